@@ -4,9 +4,17 @@ use incredible_helpers_layout::Arrangers;
 
 pub use crate::core::{
     io::RusticonIo,
-    model::{AppPhase, ExitFlow, MIN_SPLASH_MS, State},
+    model::{AUTOSAVE_MS, AppPhase, ExitFlow, STATUS_MS, State},
+    shared::ImportPayload,
 };
 
+use crate::core::config::{config, take_config_note};
+use crate::features::{
+    export::document_svg,
+    gallery::recent_entries,
+    session::{session_from_json, session_to_json},
+    templates::templates,
+};
 use crate::platform;
 use crate::screens;
 use crate::ui;
@@ -21,7 +29,74 @@ pub fn build() -> App<State> {
         state.flow.message_color = 196;
         state.flow.exit_flow = ExitFlow::None;
         state.editor.save_flag = false;
-        Globals::set_tick_rate(10.0);
+        state.flow.recent = recent_entries(platform::get_io().recent_files());
+        Globals::set_tick_rate(config().idle_ticks as f64);
+    }
+
+    fn start_editing(state: &mut State, mut payload: ImportPayload) {
+        let session = payload.session.take();
+        state.editor.load(payload);
+        // The import already checked the session loads.
+        if let Some(session) = session {
+            let _ = session_from_json(&session, &mut state.editor);
+        }
+        resume_editing(state);
+    }
+
+    /// Shows the editor on the document already in `state.editor`.
+    fn resume_editing(state: &mut State) {
+        Globals::set_tick_rate(config().editing_ticks as f64);
+        state.flow.phase = AppPhase::Main;
+        // Lines of config.toml that were ignored are noted the first time.
+        state.flow.status = take_config_note();
+        platform::get_io().remember_recent(&state.editor.file_path, &document_svg(&state.editor));
+        state.flow.autosaved_ms = Globals::now();
+        state.flow.autosaved = session_to_json(&state.editor);
+    }
+
+    /// Writes the session to the recovery file every `AUTOSAVE_MS`.
+    fn autosave(state: &mut State, now: f64) {
+        if now - state.flow.autosaved_ms < AUTOSAVE_MS {
+            return;
+        }
+        state.flow.autosaved_ms = now;
+        write_autosave(state);
+    }
+
+    /// Writes the session to the recovery file when it changed since it was
+    /// opened, saved or last autosaved.
+    fn write_autosave(state: &mut State) {
+        let session = session_to_json(&state.editor);
+        if session != state.flow.autosaved {
+            platform::get_io().autosave(&session);
+            state.flow.autosaved = session;
+        }
+    }
+
+    /// Clears the status `STATUS_MS` after it changed, along with a
+    /// confirmation still waiting for its key. The text stamp keeps its status
+    /// while it is typed. Returns whether the status was cleared.
+    fn expire_status(state: &mut State, now: f64) -> bool {
+        if state.flow.status != state.flow.status_seen {
+            state.flow.status_seen = state.flow.status.clone();
+            state.flow.status_ms = now;
+            return false;
+        }
+        let expired = state.flow.status.is_some()
+            && state.editor.stamp.is_none()
+            && now - state.flow.status_ms >= STATUS_MS;
+        if expired {
+            state.flow.status = None;
+            state.flow.status_seen = None;
+            state.flow.confirm = None;
+        }
+        expired
+    }
+
+    fn show_error(state: &mut State, err_msg: String) {
+        state.flow.phase = AppPhase::Message;
+        state.flow.message_text = Some(err_msg);
+        state.flow.message_color = 196;
     }
 
     let app = App::new(AppOptions {
@@ -34,8 +109,13 @@ pub fn build() -> App<State> {
     app.on_window(|el: &App<State>, state: &mut State, event: &EventWindow| {
         if event.window == Window::Resize {
             el.elements_to_center();
-            state.flow.viewport_too_small =
-                Platform::columns() < ui::APP_WIDTH || Platform::rows() < ui::APP_HEIGHT;
+            let (cols, rows) = (Platform::columns(), Platform::rows());
+            state.flow.viewport_too_small = cols < ui::APP_WIDTH || rows < ui::APP_HEIGHT;
+            state.flow.spare = (
+                cols.saturating_sub(ui::APP_WIDTH) / 2,
+                rows.saturating_sub(ui::APP_HEIGHT) / 2,
+            );
+            screens::editor::ui::utils::clamp_pan_in_state(state);
             el.draw();
         }
     })
@@ -44,7 +124,12 @@ pub fn build() -> App<State> {
 
         if state.editor.save_requested {
             state.editor.save_requested = false;
-            io.perform_save(state);
+            // A failed save keeps the recovery file and autosaving.
+            if io.perform_save(state).is_ok() {
+                io.remember_recent(&state.editor.file_path, &document_svg(&state.editor));
+                io.clear_autosave();
+                state.flow.autosaved = session_to_json(&state.editor);
+            }
         }
 
         if let Some(handle) = io.take_pending_handle() {
@@ -61,7 +146,13 @@ pub fn build() -> App<State> {
             return;
         }
 
+        if expire_status(state, Globals::now()) {
+            el.draw();
+        }
+
         if state.flow.exit_flow == ExitFlow::ExitRequested {
+            // Unsaved changes stay recoverable, an accidental exit loses nothing.
+            write_autosave(state);
             if io.return_to_launch_on_exit() {
                 back_to_launch(state);
                 if state.flow.phase != phase_before {
@@ -73,13 +164,51 @@ pub fn build() -> App<State> {
             return;
         }
 
+        if state.flow.phase == AppPhase::Recover {
+            if let Some(restore) = state.flow.recovery_restore.take() {
+                match state.flow.recovery.take().filter(|_| restore) {
+                    Some(editor) => {
+                        state.editor = editor;
+                        state.editor.file_handle = None;
+                        resume_editing(state);
+                    }
+                    None => {
+                        io.clear_autosave();
+                        state.flow.phase = state.flow.recovery_return.clone();
+                    }
+                }
+                el.draw();
+            }
+            return;
+        }
+
         if state.flow.phase == AppPhase::Launch {
+            if let Some(index) = state.flow.recent_open.take() {
+                if let Some(entry) = state.flow.recent.get(index).cloned() {
+                    state.editor.file_handle = None;
+                    start_editing(state, entry.payload);
+                    el.draw();
+                }
+                return;
+            }
+
             if !state.flow.launch_start_new {
                 return;
             }
 
             if !state.flow.launch_import_started {
                 state.flow.launch_start_new = false;
+
+                // Templates other than the blank one start from their drawing.
+                let template = state.flow.launch_template;
+                if let Some(template) = templates().get(template).filter(|_| template > 0) {
+                    let mut payload = template.payload.clone();
+                    payload.path = state.editor.file_path.clone();
+                    start_editing(state, payload);
+                    el.draw();
+                    return;
+                }
+
                 state.flow.launch_import_started = true;
                 io.start_import(state.editor.file_path.clone());
             }
@@ -87,30 +216,8 @@ pub fn build() -> App<State> {
             if let Some(import_result) = io.take_import_result() {
                 state.flow.launch_import_started = false;
                 match import_result {
-                    Ok((data, palette, icon_size, returned_path)) => {
-                        Globals::set_tick_rate(33.0);
-                        state.flow.phase = AppPhase::Main;
-                        state.editor.file_path = returned_path;
-                        state.editor.size = icon_size;
-                        state.editor.paintbrush = palette[0];
-                        state.editor.palette_index = 0;
-                        state.editor.palette_colors = palette;
-                        state.editor.picker_mode = false;
-                        state.editor.candidate = None;
-
-                        if icon_size == 16 {
-                            state.editor.canvas16_data = data;
-                            state.editor.canvas8_data = vec![None; 8 * 8];
-                        } else {
-                            state.editor.canvas8_data = data;
-                            state.editor.canvas16_data = vec![None; 16 * 16];
-                        }
-                    }
-                    Err(err_msg) => {
-                        state.flow.phase = AppPhase::Message;
-                        state.flow.message_text = Some(err_msg);
-                        state.flow.message_color = 196;
-                    }
+                    Ok(payload) => start_editing(state, payload),
+                    Err(err_msg) => show_error(state, err_msg),
                 }
             }
 
@@ -121,6 +228,26 @@ pub fn build() -> App<State> {
         }
 
         if state.flow.phase != AppPhase::Splash {
+            if state.flow.phase == AppPhase::Gallery && state.flow.gallery_open {
+                state.flow.gallery_open = false;
+                let index = state.flow.gallery_index;
+                if let Some(entry) = state.flow.gallery.get(index).cloned() {
+                    state.editor.file_handle = None;
+                    start_editing(state, entry.payload);
+                    el.draw();
+                }
+                return;
+            }
+
+            // Animation playback advances on the loop tick.
+            if state.flow.phase == AppPhase::Main && state.editor.advance_playback(Globals::now()) {
+                el.draw();
+            }
+
+            if state.flow.phase == AppPhase::Main {
+                autosave(state, Globals::now());
+            }
+
             if state.flow.phase == AppPhase::Message {
                 // messages can be cleared by clicking or after timeout,
                 // but for now we just let them stay or be handled by specific logic.
@@ -140,37 +267,15 @@ pub fn build() -> App<State> {
             .map(|start| Globals::now() - start)
             .unwrap_or(0.0);
 
-        if splash_elapsed < MIN_SPLASH_MS {
+        if splash_elapsed < config().splash_duration() {
             return;
         }
 
         if let Some(import_result) = io.take_import_result() {
             state.flow.launch_import_started = false;
             match import_result {
-                Ok((data, palette, icon_size, returned_path)) => {
-                    Globals::set_tick_rate(33.0);
-                    state.flow.phase = AppPhase::Main;
-                    state.editor.file_path = returned_path;
-                    state.editor.size = icon_size;
-                    state.editor.paintbrush = palette[0];
-                    state.editor.palette_index = 0;
-                    state.editor.palette_colors = palette;
-                    state.editor.picker_mode = false;
-                    state.editor.candidate = None;
-
-                    if icon_size == 16 {
-                        state.editor.canvas16_data = data;
-                        state.editor.canvas8_data = vec![None; 8 * 8];
-                    } else {
-                        state.editor.canvas8_data = data;
-                        state.editor.canvas16_data = vec![None; 16 * 16];
-                    }
-                }
-                Err(err_msg) => {
-                    state.flow.phase = AppPhase::Message;
-                    state.flow.message_text = Some(err_msg);
-                    state.flow.message_color = 196;
-                }
+                Ok(payload) => start_editing(state, payload),
+                Err(err_msg) => show_error(state, err_msg),
             }
         }
 
@@ -179,9 +284,25 @@ pub fn build() -> App<State> {
         }
     });
 
+    app.on_key(|_el, state, event| {
+        if state.flow.viewport_too_small {
+            return;
+        }
+        match state.flow.phase {
+            AppPhase::Main => screens::editor::keyboard::handle_key(state, &event.key),
+            AppPhase::Gallery => screens::gallery::keyboard::handle_key(state, &event.key),
+            AppPhase::Recover => screens::recover::keyboard::handle_key(state, &event.key),
+            AppPhase::Settings => screens::settings::keyboard::handle_key(state, &event.key),
+            _ => {}
+        }
+    });
+
     app.add(screens::launch::screen::build());
     app.add(screens::splash::screen::build());
     app.add(screens::editor::screen::build());
+    app.add(screens::gallery::screen::build());
+    app.add(screens::recover::screen::build());
+    app.add(screens::settings::screen::build());
     app.add(screens::message::screen::build());
     app.add(ui::title_bar::build());
     app.add(ui::viewport_guard::build());
@@ -0,0 +1,214 @@
+use incredible::*;
+
+use crate::core::model::Vision;
+
+/// RGB value of an ANSI 256 color code.
+pub fn ansi8_to_rgb(code: u8) -> [u8; 3] {
+    let hex = Colors::ansi8_to_hex(code);
+    let hex = hex.trim_start_matches('#');
+    let channel = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|s| u8::from_str_radix(s, 16).ok())
+            .unwrap_or(0)
+    };
+    [channel(0), channel(2), channel(4)]
+}
+
+/// RGBA pixels (row major) for a square canvas, transparent where void.
+pub fn canvas_to_rgba(data: &[Option<u8>]) -> Vec<u8> {
+    data.iter()
+        .flat_map(|cell| match cell {
+            Some(code) => {
+                let [r, g, b] = ansi8_to_rgb(*code);
+                [r, g, b, 255]
+            }
+            None => [0, 0, 0, 0],
+        })
+        .collect()
+}
+
+/// Black or white, whichever reads better on top of `code`.
+pub fn contrast_color(code: u8) -> u8 {
+    let [r, g, b] = ansi8_to_rgb(code);
+    let luma = 299 * r as u32 + 587 * g as u32 + 114 * b as u32;
+    if luma > 128_000 { 16 } else { 231 }
+}
+
+fn srgb_to_linear(channel: u8) -> f32 {
+    let c = channel as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(channel: f32) -> u8 {
+    let c = channel.clamp(0.0, 1.0);
+    let c = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round() as u8
+}
+
+/// OKLab (L, a, b) of an sRGB color.
+pub fn rgb_to_oklab(rgb: [u8; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// sRGB color of an OKLab (L, a, b) value, clamped to the sRGB gamut.
+pub fn oklab_to_rgb(lab: [f32; 3]) -> [u8; 3] {
+    let [lightness, a, b] = lab;
+
+    let l = (lightness + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m = (lightness - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s = (lightness - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+
+    [
+        4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+        -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+        -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+    ]
+    .map(linear_to_srgb)
+}
+
+/// Perceptual distance between two ANSI colors (OKLab, scaled so 1.0 is
+/// roughly a just noticeable difference).
+pub fn color_distance(a: u8, b: u8) -> f32 {
+    let [l1, a1, b1] = rgb_to_oklab(ansi8_to_rgb(a));
+    let [l2, a2, b2] = rgb_to_oklab(ansi8_to_rgb(b));
+    ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt() * 100.0
+}
+
+/// ANSI color `t` of the way from `from` to `to`, blended in OKLab.
+pub fn blend(from: u8, to: u8, t: f32) -> u8 {
+    let start = rgb_to_oklab(ansi8_to_rgb(from));
+    let end = rgb_to_oklab(ansi8_to_rgb(to));
+    let mix = [0, 1, 2].map(|i| start[i] + (end[i] - start[i]) * t);
+    Colors::rgb_to_ansi8(oklab_to_rgb(mix))
+}
+
+/// Colors used across `variants` with their pixel counts, most used first.
+pub fn color_usage<V: AsRef<[Option<u8>]>>(variants: &[V]) -> Vec<(u8, usize)> {
+    let mut counts = [0usize; 256];
+    for &code in variants.iter().flat_map(|v| v.as_ref()).flatten() {
+        counts[code as usize] += 1;
+    }
+
+    let mut usage: Vec<(u8, usize)> = (0..=255u8)
+        .map(|code| (code, counts[code as usize]))
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    usage.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+    usage
+}
+
+/// Sort key ordering colors by hue: grays first from dark to light, then the
+/// OKLCh hue angle.
+pub fn hue_key(code: u8) -> (bool, f32) {
+    let [l, a, b] = rgb_to_oklab(ansi8_to_rgb(code));
+    let chroma = (a * a + b * b).sqrt();
+    if chroma < 0.02 {
+        (false, l)
+    } else {
+        (true, b.atan2(a).rem_euclid(std::f32::consts::TAU))
+    }
+}
+
+/// Color vision deficiency matrices in linear RGB (Machado et al. 2009,
+/// full severity).
+const PROTANOPIA: [[f32; 3]; 3] = [
+    [0.152_286, 1.052_583, -0.204_868],
+    [0.114_503, 0.786_281, 0.099_216],
+    [-0.003_882, -0.048_116, 1.051_998],
+];
+const DEUTERANOPIA: [[f32; 3]; 3] = [
+    [0.367_322, 0.860_646, -0.227_968],
+    [0.280_085, 0.672_501, 0.047_413],
+    [-0.011_820, 0.042_940, 0.968_881],
+];
+const TRITANOPIA: [[f32; 3]; 3] = [
+    [1.255_528, -0.076_749, -0.178_779],
+    [-0.078_411, 0.930_809, 0.147_602],
+    [0.004_733, 0.691_367, 0.303_900],
+];
+
+/// How `code` looks with the given color vision, as the nearest ANSI color.
+pub fn simulate_vision(code: u8, vision: Vision) -> u8 {
+    let linear = ansi8_to_rgb(code).map(srgb_to_linear);
+    let matrix = match vision {
+        Vision::Normal => return code,
+        Vision::Protanopia => PROTANOPIA,
+        Vision::Deuteranopia => DEUTERANOPIA,
+        Vision::Tritanopia => TRITANOPIA,
+        Vision::Grayscale => {
+            let y = relative_luminance(ansi8_to_rgb(code));
+            return Colors::rgb_to_ansi8([linear_to_srgb(y); 3]);
+        }
+    };
+    let rgb = matrix.map(|row| row.iter().zip(linear).map(|(m, c)| m * c).sum::<f32>());
+    Colors::rgb_to_ansi8(rgb.map(linear_to_srgb))
+}
+
+/// WCAG relative luminance of an sRGB color.
+pub fn relative_luminance(rgb: [u8; 3]) -> f32 {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+/// WCAG contrast ratio between two sRGB colors (1 to 21).
+pub fn contrast_ratio(a: [u8; 3], b: [u8; 3]) -> f32 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: [f32; 3], b: [f32; 3]) -> bool {
+        (0..3).all(|i| (a[i] - b[i]).abs() < 1e-3)
+    }
+
+    #[test]
+    fn oklab_of_white_and_black() {
+        assert!(close(rgb_to_oklab([255, 255, 255]), [1.0, 0.0, 0.0]));
+        assert!(close(rgb_to_oklab([0, 0, 0]), [0.0, 0.0, 0.0]));
+        // Reference value from the OKLab definition.
+        assert!(close(rgb_to_oklab([255, 0, 0]), [0.628, 0.2249, 0.1258]));
+    }
+
+    #[test]
+    fn oklab_round_trips() {
+        for rgb in [
+            [0, 0, 0],
+            [255, 255, 255],
+            [255, 0, 0],
+            [0, 255, 0],
+            [0, 0, 255],
+            [95, 135, 175],
+            [18, 52, 86],
+            [200, 100, 7],
+        ] {
+            assert_eq!(oklab_to_rgb(rgb_to_oklab(rgb)), rgb);
+        }
+    }
+
+    #[test]
+    fn out_of_gamut_oklab_is_clamped() {
+        assert_eq!(oklab_to_rgb([2.0, 0.0, 0.0]), [255, 255, 255]);
+        assert_eq!(oklab_to_rgb([-1.0, 0.0, 0.0]), [0, 0, 0]);
+    }
+}
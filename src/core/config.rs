@@ -0,0 +1,587 @@
+use crate::core::{
+    color::ansi8_to_rgb,
+    model::{CANVAS_SIZES, MAX_PALETTE_SLOTS},
+};
+use incredible::*;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+
+/// Pixel sizes offered for saved SVGs.
+pub const EXPORT_PIXEL_SIZES: [usize; 4] = [8, 16, 32, 64];
+/// Splash durations offered, in milliseconds.
+pub const SPLASH_DURATIONS: [u32; 5] = [500, 1000, 2000, 3000, 5000];
+/// Screen updates per second allowed, from `config.toml` only.
+pub const TICK_RATES: std::ops::RangeInclusive<u32> = 1..=120;
+
+/// Keys the editor keeps for moving the cursor, painting, palette slots,
+/// zoom, size and panels, which no action can be bound to.
+pub const RESERVED_KEYS: &str = "hjkl []+-=<>{}";
+
+/// Editor shortcuts that can be bound to other keys.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyAction {
+    Fill,
+    Pick,
+    Swap,
+    Transparent,
+    Clear,
+    New,
+    Save,
+    Palette,
+    Icons,
+    Settings,
+    Exit,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 11] = [
+        KeyAction::Fill,
+        KeyAction::Pick,
+        KeyAction::Swap,
+        KeyAction::Transparent,
+        KeyAction::Clear,
+        KeyAction::New,
+        KeyAction::Save,
+        KeyAction::Palette,
+        KeyAction::Icons,
+        KeyAction::Settings,
+        KeyAction::Exit,
+    ];
+
+    /// Key of the action in the `[keys]` table.
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyAction::Fill => "fill",
+            KeyAction::Pick => "pick",
+            KeyAction::Swap => "swap",
+            KeyAction::Transparent => "transparent",
+            KeyAction::Clear => "clear",
+            KeyAction::New => "new",
+            KeyAction::Save => "save",
+            KeyAction::Palette => "palette",
+            KeyAction::Icons => "icons",
+            KeyAction::Settings => "settings",
+            KeyAction::Exit => "exit",
+        }
+    }
+
+    /// Actions that lose work, asking for their key a second time.
+    pub fn is_destructive(self) -> bool {
+        matches!(self, KeyAction::Clear | KeyAction::New | KeyAction::Exit)
+    }
+
+    pub fn default_key(self) -> char {
+        match self {
+            KeyAction::Fill => 'f',
+            KeyAction::Pick => 'p',
+            KeyAction::Swap => 'x',
+            KeyAction::Transparent => 'e',
+            KeyAction::Clear => 'c',
+            KeyAction::New => 'n',
+            KeyAction::Save => 's',
+            KeyAction::Palette => 'i',
+            KeyAction::Icons => 'o',
+            KeyAction::Settings => ',',
+            KeyAction::Exit => 'q',
+        }
+    }
+}
+
+/// User preferences, read from `config.toml` (native) or local storage (web).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// Size and file name of new icons.
+    pub size: u8,
+    pub file_name: String,
+    /// Palette new icons start with (empty slots when none).
+    pub palette: Vec<u8>,
+    /// Side of one icon pixel in saved SVGs.
+    pub export_px: usize,
+    /// Save layers as SVG groups rather than flattened.
+    pub export_layers: bool,
+    /// Show the splash screen, and for how long at least.
+    pub splash: bool,
+    pub splash_ms: u32,
+    /// One key per action, in `KeyAction::ALL` order.
+    pub keys: [char; KeyAction::ALL.len()],
+    /// Screen updates per second outside the editor and while editing.
+    pub idle_ticks: u32,
+    pub editing_ticks: u32,
+    /// Theme colors: background of selected items, grid lines and guides.
+    pub highlight: u8,
+    pub grid: u8,
+    pub guide: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            size: 8,
+            file_name: "favicon.svg".to_string(),
+            palette: vec![],
+            export_px: 32,
+            export_layers: true,
+            splash: true,
+            splash_ms: 2000,
+            keys: KeyAction::ALL.map(KeyAction::default_key),
+            idle_ticks: 10,
+            editing_ticks: 33,
+            highlight: 250,
+            grid: 240,
+            guide: 208,
+        }
+    }
+}
+
+impl Config {
+    pub fn key(&self, action: KeyAction) -> char {
+        self.keys[action as usize]
+    }
+
+    /// Binds `c` to `action`. An action already on `c` takes over the old
+    /// key, so every action keeps a key of its own. Reserved and control
+    /// keys are refused.
+    pub fn bind(&mut self, action: KeyAction, c: char) -> Result<(), String> {
+        if RESERVED_KEYS.contains(c) || c.is_control() {
+            return Err(format!("'{}' is reserved", c));
+        }
+        let old = self.key(action);
+        if let Some(other) = self.action(c) {
+            self.keys[other as usize] = old;
+        }
+        self.keys[action as usize] = c;
+        Ok(())
+    }
+
+    /// Action bound to `c`, if any.
+    pub fn action(&self, c: char) -> Option<KeyAction> {
+        KeyAction::ALL
+            .into_iter()
+            .find(|&action| self.key(action) == c)
+    }
+
+    /// How long the splash screen shows at least, in milliseconds.
+    pub fn splash_duration(&self) -> f64 {
+        if self.splash {
+            self.splash_ms as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+static CONFIG: LazyLock<RwLock<Arc<Config>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(Config::default())));
+
+/// The preferences in effect.
+pub fn config() -> Arc<Config> {
+    CONFIG.read().unwrap().clone()
+}
+
+pub fn set_config(config: Config) {
+    *CONFIG.write().unwrap() = Arc::new(config);
+}
+
+static CONFIG_NOTE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Puts the preferences of a `config.toml` text in effect, keeping a note on
+/// the lines that were ignored.
+pub fn load_config(text: &str) {
+    let (config, ignored) = parse_config(text);
+    set_config(config);
+    *CONFIG_NOTE.lock().unwrap() = ignored_note(&ignored);
+}
+
+/// The note on ignored `config.toml` lines, once.
+pub fn take_config_note() -> Option<String> {
+    CONFIG_NOTE.lock().unwrap().take()
+}
+
+/// A value of the TOML subset written by `config_to_toml`.
+#[derive(Debug, PartialEq)]
+enum Value {
+    /// A basic (`"..."`) or literal (`'...'`) string.
+    Text(String),
+    /// A number or boolean.
+    Bare(String),
+    /// An array of strings, on one line.
+    List(Vec<String>),
+}
+
+/// A `key = value` line and the table it is in.
+struct Field {
+    line: usize,
+    section: String,
+    key: String,
+    value: Value,
+}
+
+/// The string at the start of `text`, and what follows it. Basic strings
+/// resolve `\"` `\\` `\n` `\t` escapes, literal strings are taken as they are.
+fn string(text: &str) -> Option<(String, &str)> {
+    let mut chars = text.char_indices();
+    let quote = chars
+        .next()
+        .map(|(_, c)| c)
+        .filter(|c| *c == '"' || *c == '\'')?;
+    let mut out = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            _ if c == quote => return Some((out, &text[i + 1..])),
+            '\\' if quote == '"' => out.push(match chars.next()?.1 {
+                'n' => '\n',
+                't' => '\t',
+                c @ ('"' | '\\') => c,
+                _ => return None,
+            }),
+            _ => out.push(c),
+        }
+    }
+    None
+}
+
+/// `text` as a basic string, read back by `string`.
+fn quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+/// The value at the start of `text`, and what follows it.
+fn value(text: &str) -> Option<(Value, &str)> {
+    match text.chars().next()? {
+        '"' | '\'' => string(text).map(|(text, rest)| (Value::Text(text), rest)),
+        '[' => {
+            let mut items = vec![];
+            let mut rest = text[1..].trim_start();
+            loop {
+                if let Some(rest) = rest.strip_prefix(']') {
+                    return Some((Value::List(items), rest));
+                }
+                let (item, after) = string(rest)?;
+                items.push(item);
+                rest = after.trim_start();
+                match rest.strip_prefix(',') {
+                    Some(after) => rest = after.trim_start(),
+                    None if rest.starts_with(']') => {}
+                    None => return None,
+                }
+            }
+        }
+        _ => {
+            let end = text.find('#').unwrap_or(text.len());
+            let bare = text[..end].trim();
+            (!bare.is_empty()).then(|| (Value::Bare(bare.to_string()), &text[end..]))
+        }
+    }
+}
+
+/// Whether `rest` of a line is blank or a `# comment`.
+fn is_line_end(rest: &str) -> bool {
+    let rest = rest.trim();
+    rest.is_empty() || rest.starts_with('#')
+}
+
+/// The fields of a TOML text, and the numbers of the lines that could not
+/// be read. One `key = value` per line is understood.
+fn read_fields(text: &str) -> (Vec<Field>, Vec<usize>) {
+    let mut fields = vec![];
+    let mut ignored = vec![];
+    let mut section = String::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[') {
+            section = match header.split_once(']') {
+                Some((name, rest)) if is_line_end(rest) => name.trim().to_string(),
+                // Keys under a broken header are ignored along with it.
+                _ => {
+                    ignored.push(i + 1);
+                    String::new()
+                }
+            };
+            continue;
+        }
+        let field = line.split_once('=').and_then(|(key, rest)| {
+            let (value, rest) = value(rest.trim_start())?;
+            is_line_end(rest).then(|| Field {
+                line: i + 1,
+                section: section.clone(),
+                key: key.trim().to_string(),
+                value,
+            })
+        });
+        match field {
+            Some(field) => fields.push(field),
+            None => ignored.push(i + 1),
+        }
+    }
+    (fields, ignored)
+}
+
+/// Fields of a config text, taken one setting at a time.
+struct Fields {
+    fields: Vec<Field>,
+    ignored: Vec<usize>,
+}
+
+impl Fields {
+    /// The value of `key` in `[section]`, as `read` takes it. A value `read`
+    /// refuses notes its line as ignored.
+    fn take<T>(
+        &mut self,
+        section: &str,
+        key: &str,
+        read: impl Fn(&Value) -> Option<T>,
+    ) -> Option<T> {
+        let i = self
+            .fields
+            .iter()
+            .position(|f| f.section == section && f.key == key)?;
+        let field = self.fields.remove(i);
+        let value = read(&field.value);
+        if value.is_none() {
+            self.ignored.push(field.line);
+        }
+        value
+    }
+
+    /// Lines not read, including unknown and repeated keys, in order.
+    fn ignored(mut self) -> Vec<usize> {
+        self.ignored.extend(self.fields.iter().map(|f| f.line));
+        self.ignored.sort_unstable();
+        self.ignored
+    }
+}
+
+fn text_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Text(text) => Some(text.clone()),
+        _ => None,
+    }
+}
+
+fn number_value(value: &Value) -> Option<u32> {
+    match value {
+        Value::Bare(bare) => bare.parse().ok(),
+        _ => None,
+    }
+}
+
+fn flag_value(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bare(bare) => bare.parse().ok(),
+        _ => None,
+    }
+}
+
+fn code_value(value: &Value) -> Option<u8> {
+    number_value(value).and_then(|v| u8::try_from(v).ok())
+}
+
+/// Colors of a `["#rrggbb", ...]` array, snapped to ANSI codes.
+fn palette_value(value: &Value) -> Option<Vec<u8>> {
+    let Value::List(items) = value else {
+        return None;
+    };
+    items
+        .iter()
+        .take(MAX_PALETTE_SLOTS)
+        .map(|item| {
+            let hex = item.trim_start_matches('#');
+            let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+            Some(Colors::rgb_to_ansi8([
+                channel(0)?,
+                channel(2)?,
+                channel(4)?,
+            ]))
+        })
+        .collect()
+}
+
+/// Preferences in a `config.toml` text, and the numbers of the lines that
+/// were ignored. Missing or invalid settings keep their defaults.
+pub fn parse_config(text: &str) -> (Config, Vec<usize>) {
+    let mut config = Config::default();
+    let (fields, ignored) = read_fields(text);
+    let mut fields = Fields { fields, ignored };
+
+    let size = fields.take("defaults", "size", |v| {
+        code_value(v).filter(|s| CANVAS_SIZES.contains(s))
+    });
+    config.size = size.unwrap_or(config.size);
+    let name = fields.take("defaults", "file_name", |v| {
+        text_value(v).filter(|n| !n.trim().is_empty())
+    });
+    config.file_name = name.unwrap_or(config.file_name);
+    config.palette = fields
+        .take("defaults", "palette", palette_value)
+        .unwrap_or(config.palette);
+    let px = fields.take("export", "pixel_size", |v| {
+        number_value(v)
+            .map(|v| v as usize)
+            .filter(|px| EXPORT_PIXEL_SIZES.contains(px))
+    });
+    config.export_px = px.unwrap_or(config.export_px);
+    config.export_layers = fields
+        .take("export", "layers", flag_value)
+        .unwrap_or(config.export_layers);
+    config.splash = fields
+        .take("splash", "show", flag_value)
+        .unwrap_or(config.splash);
+    let ms = fields.take("splash", "duration_ms", |v| {
+        number_value(v).filter(|ms| *ms <= 10000)
+    });
+    config.splash_ms = ms.unwrap_or(config.splash_ms);
+    for action in KeyAction::ALL {
+        let key = fields.take("keys", action.name(), |v| {
+            let key = text_value(v)?;
+            let mut chars = key.chars();
+            let c = chars.next().filter(|_| chars.next().is_none())?;
+            (!RESERVED_KEYS.contains(c) && !c.is_control()).then_some(c)
+        });
+        if let Some(c) = key {
+            // Refused keys were checked for above.
+            let _ = config.bind(action, c);
+        }
+    }
+    let tick_rate = |v: &Value| number_value(v).filter(|rate| TICK_RATES.contains(rate));
+    config.idle_ticks = fields
+        .take("ticks", "idle", tick_rate)
+        .unwrap_or(config.idle_ticks);
+    config.editing_ticks = fields
+        .take("ticks", "editing", tick_rate)
+        .unwrap_or(config.editing_ticks);
+    config.highlight = fields
+        .take("theme", "highlight", code_value)
+        .unwrap_or(config.highlight);
+    config.grid = fields
+        .take("theme", "grid", code_value)
+        .unwrap_or(config.grid);
+    config.guide = fields
+        .take("theme", "guide", code_value)
+        .unwrap_or(config.guide);
+    (config, fields.ignored())
+}
+
+/// A note naming the `lines` of `config.toml` that were ignored, if any.
+pub fn ignored_note(lines: &[usize]) -> Option<String> {
+    let numbers: Vec<String> = lines.iter().map(usize::to_string).collect();
+    match numbers.len() {
+        0 => None,
+        1 => Some(format!("config.toml line {} ignored", numbers[0])),
+        _ => Some(format!("config.toml lines {} ignored", numbers.join(", "))),
+    }
+}
+
+/// `config` as a `config.toml` text, read back by `parse_config`.
+pub fn config_to_toml(config: &Config) -> String {
+    let palette: Vec<String> = config
+        .palette
+        .iter()
+        .map(|&code| {
+            let [r, g, b] = ansi8_to_rgb(code);
+            format!("\"#{:02x}{:02x}{:02x}\"", r, g, b)
+        })
+        .collect();
+
+    let mut out = String::from("# Rusticon settings\n\n[defaults]\n");
+    out.push_str(&format!("size = {}\n", config.size));
+    out.push_str(&format!("file_name = {}\n", quote(&config.file_name)));
+    out.push_str(&format!("palette = [{}]\n", palette.join(", ")));
+    out.push_str("\n[export]\n");
+    out.push_str(&format!("pixel_size = {}\n", config.export_px));
+    out.push_str(&format!("layers = {}\n", config.export_layers));
+    out.push_str("\n[splash]\n");
+    out.push_str(&format!("show = {}\n", config.splash));
+    out.push_str(&format!("duration_ms = {}\n", config.splash_ms));
+    out.push_str("\n[keys]\n");
+    for action in KeyAction::ALL {
+        let key = config.key(action).to_string();
+        out.push_str(&format!("{} = {}\n", action.name(), quote(&key)));
+    }
+    out.push_str("\n# Screen updates per second\n[ticks]\n");
+    out.push_str(&format!("idle = {}\n", config.idle_ticks));
+    out.push_str(&format!("editing = {}\n", config.editing_ticks));
+    out.push_str("\n# ANSI color codes (0-255)\n[theme]\n");
+    out.push_str(&format!("highlight = {}\n", config.highlight));
+    out.push_str(&format!("grid = {}\n", config.grid));
+    out.push_str(&format!("guide = {}\n", config.guide));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_round_trips() {
+        let mut config = Config {
+            size: 16,
+            file_name: "my \"icon\"\\ #1.svg".to_string(),
+            palette: vec![196, 46, 21],
+            export_px: 64,
+            export_layers: false,
+            splash: false,
+            splash_ms: 500,
+            idle_ticks: 5,
+            editing_ticks: 60,
+            highlight: 17,
+            grid: 236,
+            guide: 160,
+            ..Config::default()
+        };
+        config.bind(KeyAction::Save, 'w').unwrap();
+        config.bind(KeyAction::Fill, '#').unwrap();
+        config.bind(KeyAction::Pick, '\'').unwrap();
+
+        assert_eq!(parse_config(&config_to_toml(&config)), (config, vec![]));
+    }
+
+    #[test]
+    fn comments_and_literal_strings_are_read() {
+        let text = "[defaults] # new icons\n\
+                    size = 16 # pixels\n\
+                    file_name = 'logo \\ # 2.svg' # literal\n\
+                    palette = [\"#ff0000\", '#00ff00'] # two colors\n\
+                    [keys]\n\
+                    save = \"w\" # write\n";
+        let (config, ignored) = parse_config(text);
+
+        assert_eq!(config.size, 16);
+        assert_eq!(config.file_name, "logo \\ # 2.svg");
+        assert_eq!(config.palette, vec![196, 46]);
+        assert_eq!(config.key(KeyAction::Save), 'w');
+        assert!(ignored.is_empty());
+    }
+
+    #[test]
+    fn bad_lines_are_ignored_and_reported() {
+        let text = "[defaults]\n\
+                    size = 10\n\
+                    file_name = \"open.svg\n\
+                    colour = 3\n\
+                    [keys]\n\
+                    save = \"h\"\n\
+                    [ticks]\n\
+                    idle = 500\n\
+                    editing = 20\n";
+        let (config, ignored) = parse_config(text);
+
+        assert_eq!(config.size, Config::default().size);
+        assert_eq!(config.file_name, Config::default().file_name);
+        assert_eq!(config.key(KeyAction::Save), 's');
+        assert_eq!(config.idle_ticks, Config::default().idle_ticks);
+        assert_eq!(config.editing_ticks, 20);
+        assert_eq!(ignored, vec![2, 3, 4, 6, 8]);
+        assert_eq!(
+            ignored_note(&ignored).as_deref(),
+            Some("config.toml lines 2, 3, 4, 6, 8 ignored")
+        );
+        assert_eq!(ignored_note(&[]), None);
+    }
+}
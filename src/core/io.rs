@@ -16,7 +16,45 @@ pub trait RusticonIo {
     fn start_import_drop(&self, item: DroppedItem);
     fn take_import_result(&self) -> Option<ImportOutcome>;
     fn report_message(&self, msg: &str, color_code: u8);
-    fn perform_save(&self, state: &State);
+    /// Saves the document, reporting and returning any failure.
+    fn perform_save(&self, state: &State) -> Result<(), String>;
+    /// Writes a file next to the icon (palette, shared session, icon set) by
+    /// its path, or downloads it in the browser.
+    fn save_file(&self, path: &str, bytes: &[u8]) -> Result<(), String>;
+    /// Bytes of the file at `path`, such as a palette next to the icon.
+    fn read_file(&self, path: &str) -> Result<Vec<u8>, String> {
+        Err(format!("Drop {} on the editor to load it", path))
+    }
+    /// Files the gallery browser looks through for icons: (path, bytes).
+    fn gallery_files(&self, _dir: &str) -> Vec<(String, Vec<u8>)> {
+        vec![]
+    }
+    /// Recently edited icons, most recent first: (path, bytes).
+    fn recent_files(&self) -> Vec<(String, Vec<u8>)> {
+        vec![]
+    }
+    /// Puts the icon at `path`, whose saved document is `svg`, first in the
+    /// recent files.
+    fn remember_recent(&self, _path: &str, _svg: &str) {}
+    /// Writes the unsaved editor session (see `features::session`) to the
+    /// recovery file.
+    fn autosave(&self, _session: &str) {}
+    /// Removes the recovery file, once the document is saved or discarded.
+    fn clear_autosave(&self) {}
+    /// The session a previous run left unsaved, when the icon it edits was
+    /// not saved since.
+    fn recovery(&self) -> Option<String> {
+        None
+    }
+    /// Text of the user's `config.toml`, if there is one.
+    fn load_config(&self) -> Option<String> {
+        None
+    }
+    fn save_config(&self, _text: &str) {}
+    /// Files in the user templates folder: (file name, bytes).
+    fn user_templates(&self) -> Vec<(String, Vec<u8>)> {
+        vec![]
+    }
     fn take_pending_handle(&self) -> Option<crate::platform::FileHandle> {
         None
     }
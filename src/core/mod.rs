@@ -1,3 +1,5 @@
+pub mod color;
+pub mod config;
 pub mod io;
 pub mod model;
 pub mod shared;
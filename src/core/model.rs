@@ -1,9 +1,151 @@
+use std::collections::BTreeMap;
+
+use crate::core::color::{color_usage, hue_key};
+use crate::core::config::{KeyAction, config};
+use crate::core::shared::ImportPayload;
+
+/// Square canvas sizes a document can hold, smallest first.
+pub const CANVAS_SIZES: [u8; 6] = [8, 12, 16, 24, 32, 48];
+
+/// A blank grid for every size in `CANVAS_SIZES`.
+pub fn blank_canvases() -> BTreeMap<u8, Vec<Option<u8>>> {
+    CANVAS_SIZES
+        .iter()
+        .map(|&size| (size, vec![None; size as usize * size as usize]))
+        .collect()
+}
+
+/// Most layers a document can hold.
+pub const MAX_LAYERS: usize = 16;
+
+/// One layer of a document: a grid per size variant, void is transparent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layer {
+    pub name: String,
+    pub visible: bool,
+    /// One grid per size variant, keyed by side length.
+    pub canvases: BTreeMap<u8, Vec<Option<u8>>>,
+}
+
+impl Layer {
+    /// A visible layer with every size blank.
+    pub fn new(name: String) -> Self {
+        Layer {
+            name,
+            visible: true,
+            canvases: blank_canvases(),
+        }
+    }
+
+    /// Grid of the given size (empty when the layer has none).
+    pub fn canvas(&self, size: u8) -> &[Option<u8>] {
+        self.canvases.get(&size).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// Most frames a document can hold.
+pub const MAX_FRAMES: usize = 16;
+/// Largest canvas zoom level, each pixel drawn as 12x6 terminal cells.
+pub const MAX_ZOOM: u8 = 6;
+/// Frame delays offered, in milliseconds.
+pub const FRAME_DELAYS: [u16; 7] = [50, 100, 150, 200, 300, 500, 1000];
+/// Most files kept in the recent files list.
+pub const MAX_RECENT: usize = 6;
+
+/// One animation frame: its layers and how long it shows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub layers: Vec<Layer>,
+    /// Milliseconds.
+    pub delay: u16,
+}
+
+impl Frame {
+    pub fn new(layers: Vec<Layer>) -> Self {
+        Frame {
+            layers,
+            delay: FRAME_DELAYS[1],
+        }
+    }
+
+    /// Visible layers of the given size flattened.
+    pub fn composite(&self, size: u8) -> Vec<Option<u8>> {
+        composite(&self.layers, size)
+    }
+
+    /// Every size variant flattened, smallest first.
+    pub fn variants(&self) -> Vec<Vec<Option<u8>>> {
+        CANVAS_SIZES
+            .iter()
+            .map(|&size| self.composite(size))
+            .collect()
+    }
+
+    /// The frame as a single layer holding its visible drawing.
+    pub fn flattened(&self) -> Frame {
+        let mut layer = Layer::new("Layer 1".to_string());
+        layer.canvases = CANVAS_SIZES
+            .iter()
+            .map(|&size| (size, self.composite(size)))
+            .collect();
+        Frame {
+            layers: vec![layer],
+            delay: self.delay,
+        }
+    }
+}
+
+/// Merges `layers[index]` into the layer below it, the upper layer winning
+/// where it is not void.
+fn merge_down(layers: &mut Vec<Layer>, index: usize) {
+    if index == 0 || index >= layers.len() {
+        return;
+    }
+    let upper = layers.remove(index);
+    let lower = &mut layers[index - 1];
+    for (size, grid) in upper.canvases {
+        let target = lower
+            .canvases
+            .entry(size)
+            .or_insert_with(|| vec![None; grid.len()]);
+        for (cell, color) in target.iter_mut().zip(grid) {
+            if color.is_some() {
+                *cell = color;
+            }
+        }
+    }
+}
+
+/// Visible `layers` of the given size stacked bottom to top, void letting the
+/// layers below show through.
+pub fn composite(layers: &[Layer], size: u8) -> Vec<Option<u8>> {
+    let mut out = vec![None; size as usize * size as usize];
+    for layer in layers.iter().filter(|layer| layer.visible) {
+        for (cell, &color) in out.iter_mut().zip(layer.canvas(size)) {
+            if color.is_some() {
+                *cell = color;
+            }
+        }
+    }
+    out
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum AppPhase {
     Launch,
     Splash,
     Main,
     Message,
+    Gallery,
+    Recover,
+    Settings,
+}
+
+/// An icon found by the gallery browser.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GalleryEntry {
+    pub name: String,
+    pub payload: ImportPayload,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -12,15 +154,371 @@ pub enum ExitFlow {
     ExitRequested,
 }
 
+/// Side panel shown in place of the color pickers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Panel {
+    Colors,
+    Preview,
+    Usage,
+    Layers,
+    Export,
+}
+
+impl Panel {
+    pub const ALL: [Panel; 5] = [
+        Panel::Colors,
+        Panel::Preview,
+        Panel::Usage,
+        Panel::Layers,
+        Panel::Export,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Panel::Colors => "Colors",
+            Panel::Preview => "Preview",
+            Panel::Usage => "Usage",
+            Panel::Layers => "Layers",
+            Panel::Export => "Export",
+        }
+    }
+}
+
+/// Corner radii offered for the rounded mask, in percent of the side (50 is
+/// a circle).
+pub const EXPORT_RADII: [u8; 4] = [0, 10, 20, 50];
+/// Padding offered around the drawing, in icon pixels.
+pub const EXPORT_PADDINGS: [u8; 4] = [0, 1, 2, 4];
+
+/// Named sets of SVG export settings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportProfile {
+    Custom,
+    Favicon,
+    AppIcon,
+    Avatar,
+}
+
+impl ExportProfile {
+    pub const ALL: [ExportProfile; 4] = [
+        ExportProfile::Custom,
+        ExportProfile::Favicon,
+        ExportProfile::AppIcon,
+        ExportProfile::Avatar,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportProfile::Custom => "custom",
+            ExportProfile::Favicon => "favicon",
+            ExportProfile::AppIcon => "app-icon",
+            ExportProfile::Avatar => "avatar",
+        }
+    }
+
+    /// The settings the profile stands for; custom keeps `current`.
+    pub fn options(self, current: &ExportOptions) -> ExportOptions {
+        let base = ExportOptions {
+            profile: self,
+            px: 32,
+            view_box: false,
+            background: None,
+            radius: 0,
+            padding: 0,
+        };
+        match self {
+            ExportProfile::Custom => ExportOptions {
+                profile: self,
+                ..current.clone()
+            },
+            // Scales to whatever size the browser asks for.
+            ExportProfile::Favicon => ExportOptions {
+                view_box: true,
+                ..base
+            },
+            ExportProfile::AppIcon => ExportOptions {
+                background: Some(231),
+                radius: 20,
+                padding: 1,
+                ..base
+            },
+            ExportProfile::Avatar => ExportOptions {
+                background: Some(254),
+                radius: 50,
+                padding: 2,
+                ..base
+            },
+        }
+    }
+}
+
+/// How the icon is written to SVG, kept with the document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportOptions {
+    pub profile: ExportProfile,
+    /// Side of one icon pixel in px (ignored with `view_box`).
+    pub px: usize,
+    /// Unitless: a `viewBox` of one unit per icon pixel, no width and height.
+    pub view_box: bool,
+    /// Fill behind the drawing and its padding.
+    pub background: Option<u8>,
+    /// Rounded-corner mask, in percent of the side (0 for none).
+    pub radius: u8,
+    /// Empty icon pixels around the drawing.
+    pub padding: u8,
+}
+
+impl ExportOptions {
+    /// Plain export at the configured pixel size.
+    pub fn new() -> Self {
+        ExportOptions {
+            profile: ExportProfile::Custom,
+            px: config().export_px,
+            view_box: false,
+            background: None,
+            radius: 0,
+            padding: 0,
+        }
+    }
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Palette file formats that can be loaded and saved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteFormat {
+    /// GIMP `.gpl`.
+    Gpl,
+    /// Paint.NET `.txt` (`AARRGGBB` per line).
+    PaintNet,
+    /// Plain `.hex` list (`RRGGBB` per line, as exported by Lospec).
+    Hex,
+    /// `.json` with a `colors` list of hex strings (Lospec style).
+    Json,
+}
+
+impl PaletteFormat {
+    pub const ALL: [PaletteFormat; 4] = [
+        PaletteFormat::Gpl,
+        PaletteFormat::PaintNet,
+        PaletteFormat::Hex,
+        PaletteFormat::Json,
+    ];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            PaletteFormat::Gpl => "gpl",
+            PaletteFormat::PaintNet => "txt",
+            PaletteFormat::Hex => "hex",
+            PaletteFormat::Json => "json",
+        }
+    }
+}
+
+/// Color vision simulated in the preview panel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Vision {
+    Normal,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+    Grayscale,
+}
+
+impl Vision {
+    pub const ALL: [Vision; 5] = [
+        Vision::Normal,
+        Vision::Protanopia,
+        Vision::Deuteranopia,
+        Vision::Tritanopia,
+        Vision::Grayscale,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Vision::Normal => "Normal",
+            Vision::Protanopia => "Protan",
+            Vision::Deuteranopia => "Deutan",
+            Vision::Tritanopia => "Tritan",
+            Vision::Grayscale => "Gray",
+        }
+    }
+}
+
+/// Pixel font of the text stamp.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StampFont {
+    /// 3x5, for 8x8 icons.
+    Small,
+    /// 4x6, for 16x16 icons and up.
+    Large,
+}
+
+impl StampFont {
+    pub const ALL: [StampFont; 2] = [StampFont::Small, StampFont::Large];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StampFont::Small => "3x5",
+            StampFont::Large => "4x6",
+        }
+    }
+
+    /// Glyph (width, height) in pixels.
+    pub fn glyph_size(self) -> (usize, usize) {
+        match self {
+            StampFont::Small => (3, 5),
+            StampFont::Large => (4, 6),
+        }
+    }
+
+    /// Font that suits a canvas of the given size.
+    pub fn for_size(size: u8) -> Self {
+        if size <= 8 {
+            StampFont::Small
+        } else {
+            StampFont::Large
+        }
+    }
+}
+
+/// Editor area driven by the keyboard.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Focus {
+    Canvas,
+    Palette,
+    /// The 16-color column, with the transparent swatch after it.
+    Basic,
+    Cube,
+    Gray,
+    Layers,
+    Frames,
+}
+
+impl Focus {
+    pub const ALL: [Focus; 7] = [
+        Focus::Canvas,
+        Focus::Palette,
+        Focus::Basic,
+        Focus::Cube,
+        Focus::Gray,
+        Focus::Layers,
+        Focus::Frames,
+    ];
+
+    /// Side panel the area is drawn on, when it is not always shown.
+    pub fn panel(self) -> Option<Panel> {
+        match self {
+            Focus::Basic | Focus::Cube | Focus::Gray => Some(Panel::Colors),
+            Focus::Layers => Some(Panel::Layers),
+            Focus::Canvas | Focus::Palette | Focus::Frames => None,
+        }
+    }
+}
+
+/// Canvas rendering options.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ViewOptions {
+    /// Show void cells as a two-tone checkerboard instead of `:`.
+    pub checkerboard: bool,
+    /// Lines between pixels.
+    pub grid: bool,
+    /// Lines through the center of the grid.
+    pub guides: bool,
+    /// Highlight the row and column under the cursor.
+    pub crosshair: bool,
+}
+
+/// Palette slots a new document starts with.
+pub const DEFAULT_PALETTE_SLOTS: usize = 8;
+/// Most palette slots the palette bar holds.
+pub const MAX_PALETTE_SLOTS: usize = 32;
+
+/// Pads `colors` with empty slots up to the default size and drops what
+/// does not fit the palette bar.
+pub fn fit_palette(mut colors: Vec<Option<u8>>) -> Vec<Option<u8>> {
+    colors.truncate(MAX_PALETTE_SLOTS);
+    if colors.len() < DEFAULT_PALETTE_SLOTS {
+        colors.resize(DEFAULT_PALETTE_SLOTS, None);
+    }
+    colors
+}
+
+/// Order of the colors extracted from the canvas into the palette.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PaletteOrder {
+    Frequency,
+    Hue,
+}
+
+/// Flood fill behaviour.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FillOptions {
+    /// Also spread diagonally.
+    pub connectivity8: bool,
+    /// Replace the color everywhere, connected or not.
+    pub global: bool,
+    /// Largest perceptual distance (see `color::color_distance`) still
+    /// treated as the same color.
+    pub tolerance: u8,
+    /// Blend from the primary to the secondary color across the region.
+    pub gradient: bool,
+}
+
+/// Tolerance steps offered for flood fill.
+pub const FILL_TOLERANCES: [u8; 5] = [0, 3, 6, 10, 15];
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct FlowState {
     pub phase: AppPhase,
     pub viewport_too_small: bool,
+    /// Terminal cells to spare on each side of the app (columns, rows), which
+    /// the editor spreads into to enlarge the canvas.
+    pub spare: (usize, usize),
     pub launch_start_new: bool,
     pub launch_import_started: bool,
+    /// Template picked on the launch screen (index into `templates()`).
+    pub launch_template: usize,
+    /// Directory scanned by the gallery browser, its icons and the selected one.
+    pub gallery_dir: String,
+    pub gallery: Vec<GalleryEntry>,
+    pub gallery_index: usize,
+    /// Screen the gallery browser returns to.
+    pub gallery_return: AppPhase,
+    /// Set to open the selected gallery icon in the editor.
+    pub gallery_open: bool,
+    /// Recently edited icons shown on the launch screen, and one to open.
+    pub recent: Vec<GalleryEntry>,
+    pub recent_open: Option<usize>,
+    /// Last autosave of the document to the recovery file: when, and what.
+    pub autosaved_ms: f64,
+    pub autosaved: String,
+    /// Editor left unsaved by a previous run, the screen to go to if it is
+    /// discarded, and the choice made (`true` restores).
+    pub recovery: Option<EditorState>,
+    pub recovery_return: AppPhase,
+    pub recovery_restore: Option<bool>,
+    /// Selected row of the settings screen, whether its value is being
+    /// typed, and the screen to go back to.
+    pub settings_index: usize,
+    pub settings_editing: bool,
+    pub settings_return: AppPhase,
     pub splash_started_ms: Option<f64>,
     pub message_text: Option<String>,
     pub message_color: u8,
+    /// Short note shown at the end of the title bar while editing, or in
+    /// place of the hint on the settings screen.
+    pub status: Option<String>,
+    /// The status as last seen by the loop, and when it changed to it, so it
+    /// clears after `STATUS_MS`.
+    pub status_seen: Option<String>,
+    pub status_ms: f64,
+    /// Destructive shortcut waiting for its key to be pressed again.
+    pub confirm: Option<KeyAction>,
     pub exit_flow: ExitFlow,
 }
 
@@ -28,21 +526,437 @@ pub struct FlowState {
 pub struct EditorState {
     pub candidate: Option<u8>,
     pub paintbrush: Option<u8>,
+    /// Color painted with the right mouse button (transparent by default).
+    pub secondary: Option<u8>,
     /// Color under the cursor before the last paint stroke (used to undo the
     /// second click of a double-click before flood fill).
     pub prev_color_on_canvas: Option<u8>,
     pub palette_index: usize,
     pub palette_colors: Vec<Option<u8>>,
+    /// Changing a palette slot also recolors the pixels using it.
+    pub linked_palette: bool,
+    /// Format used when saving the palette to a file.
+    pub palette_format: PaletteFormat,
+    /// Palette slots (origin, current) while a slot is being dragged.
+    pub palette_drag: Option<(usize, usize)>,
     pub picker_mode: bool,
-    pub canvas16_data: Vec<Option<u8>>,
-    pub canvas8_data: Vec<Option<u8>>,
+    /// Layers of the active frame, from bottom to top.
+    pub layers: Vec<Layer>,
+    /// Animation frames. While a frame is active its layers live in `layers`
+    /// and its entry here only keeps the delay.
+    pub frames: Vec<Frame>,
+    pub active_frame: usize,
+    /// Show the previous frame faintly under void cells.
+    pub onion_skin: bool,
+    /// When the shown frame started playing, `None` when stopped.
+    pub playing: Option<f64>,
+    /// Layer painted on.
+    pub active_layer: usize,
+    /// Save layers as SVG groups instead of flattening them.
+    pub export_layers: bool,
+    pub export: ExportOptions,
     pub size: u8,
+    /// Top-left pixel of the canvas viewport when the grid does not fit.
+    pub pan_x: usize,
+    pub pan_y: usize,
+    /// Terminal cells per pixel step (1 = 2x1, 2 = 4x2, ...), `None` to fit.
+    pub zoom: Option<u8>,
+    pub panel: Panel,
+    pub view: ViewOptions,
+    pub vision: Vision,
+    pub fill: FillOptions,
+    /// Dither when snapping the canvas to the palette.
+    pub snap_dither: bool,
+    /// Text being typed for the text stamp, `None` while the tool is off.
+    pub stamp: Option<String>,
+    pub stamp_font: StampFont,
+    /// Grid position (row, col) under the mouse while over the canvas.
+    pub hover: Option<(usize, usize)>,
+    /// Keyboard focus, `None` while editing with the mouse only.
+    pub focus: Option<Focus>,
+    /// Action chosen with the arrows while the layers or frames have focus.
+    pub focus_action: usize,
+    /// Keyboard cursor on the canvas (row, col).
+    pub cursor: (usize, usize),
     pub save_flag: bool,
     pub save_requested: bool,
     pub file_path: String,
     pub file_handle: Option<crate::platform::FileHandle>,
 }
 
+impl EditorState {
+    /// Active layer's canvas data of the active size.
+    pub fn canvas_data(&self) -> &[Option<u8>] {
+        self.layers
+            .get(self.active_layer)
+            .map(|layer| layer.canvas(self.size))
+            .unwrap_or(&[])
+    }
+
+    /// Mutable canvas data of the active layer and size, created blank if
+    /// missing.
+    pub fn canvas_data_mut(&mut self) -> &mut Vec<Option<u8>> {
+        let cells = self.size as usize * self.size as usize;
+        let index = self.active_layer.min(self.layers.len() - 1);
+        self.layers[index]
+            .canvases
+            .entry(self.size)
+            .or_insert_with(|| vec![None; cells])
+    }
+
+    /// Visible layers of the active size flattened, as shown on the canvas.
+    pub fn composite(&self) -> Vec<Option<u8>> {
+        composite(&self.layers, self.size)
+    }
+
+    /// Every size variant of the active frame flattened, smallest first.
+    pub fn variants(&self) -> Vec<Vec<Option<u8>>> {
+        CANVAS_SIZES
+            .iter()
+            .map(|&size| composite(&self.layers, size))
+            .collect()
+    }
+
+    /// Every size of every frame flattened.
+    pub fn all_variants(&self) -> Vec<Vec<Option<u8>>> {
+        (0..self.frames.len())
+            .flat_map(|i| {
+                CANVAS_SIZES
+                    .iter()
+                    .map(move |&size| composite(self.frame_layers(i), size))
+            })
+            .collect()
+    }
+
+    /// Layers of frame `index`.
+    pub fn frame_layers(&self, index: usize) -> &[Layer] {
+        if index == self.active_frame {
+            &self.layers
+        } else {
+            self.frames
+                .get(index)
+                .map(|frame| frame.layers.as_slice())
+                .unwrap_or(&[])
+        }
+    }
+
+    /// Every frame with its layers, flattened to a single layer each when
+    /// `flatten` is set.
+    pub fn document_frames(&self, flatten: bool) -> Vec<Frame> {
+        (0..self.frames.len())
+            .map(|i| {
+                let frame = Frame {
+                    layers: self.frame_layers(i).to_vec(),
+                    delay: self.frames[i].delay,
+                };
+                if flatten { frame.flattened() } else { frame }
+            })
+            .collect()
+    }
+
+    /// Frame shown under void cells by the onion skin.
+    pub fn previous_frame(&self) -> usize {
+        (self.active_frame + self.frames.len() - 1) % self.frames.len()
+    }
+
+    /// Starts or stops playing the frames in a loop.
+    pub fn toggle_playback(&mut self, now: f64) {
+        self.playing = match self.playing {
+            Some(_) => None,
+            None => Some(now),
+        };
+    }
+
+    /// Moves on to the next frame once the shown one has played its delay.
+    /// True when another frame is now shown.
+    pub fn advance_playback(&mut self, now: f64) -> bool {
+        let Some(start) = self.playing else {
+            return false;
+        };
+        if self.frames.len() < 2 || now - start < self.frames[self.active_frame].delay as f64 {
+            return false;
+        }
+        self.step_frame(true);
+        self.playing = Some(now);
+        true
+    }
+
+    /// Layer stacks of every frame, the active one first.
+    fn layer_stacks(&mut self) -> impl Iterator<Item = &mut Vec<Layer>> {
+        let active = self.active_frame;
+        std::iter::once(&mut self.layers).chain(
+            self.frames
+                .iter_mut()
+                .enumerate()
+                .filter(move |(i, _)| *i != active)
+                .map(|(_, frame)| &mut frame.layers),
+        )
+    }
+
+    /// Makes frame `index` the one shown and edited.
+    pub fn select_frame(&mut self, index: usize) {
+        if index == self.active_frame || index >= self.frames.len() {
+            return;
+        }
+        self.frames[self.active_frame].layers = std::mem::take(&mut self.layers);
+        self.layers = std::mem::take(&mut self.frames[index].layers);
+        self.active_frame = index;
+    }
+
+    /// Selects the next or previous frame, wrapping around.
+    pub fn step_frame(&mut self, forward: bool) {
+        let count = self.frames.len();
+        let next = if forward {
+            (self.active_frame + 1) % count
+        } else {
+            (self.active_frame + count - 1) % count
+        };
+        self.select_frame(next);
+    }
+
+    /// Moves the active frame to the next of `FRAME_DELAYS`, wrapping around.
+    pub fn cycle_frame_delay(&mut self) {
+        let frame = &mut self.frames[self.active_frame];
+        let next = FRAME_DELAYS
+            .iter()
+            .position(|&d| d == frame.delay)
+            .map_or(0, |idx| (idx + 1) % FRAME_DELAYS.len());
+        frame.delay = FRAME_DELAYS[next];
+    }
+
+    /// Adds a frame after the active one and selects it: a copy of the
+    /// active frame with `duplicate`, otherwise blank layers of the same names.
+    pub fn add_frame(&mut self, duplicate: bool) {
+        if self.frames.len() >= MAX_FRAMES {
+            return;
+        }
+        let layers = if duplicate {
+            self.layers.clone()
+        } else {
+            self.layers
+                .iter()
+                .map(|layer| Layer {
+                    visible: layer.visible,
+                    ..Layer::new(layer.name.clone())
+                })
+                .collect()
+        };
+        let delay = self.frames[self.active_frame].delay;
+        let index = self.active_frame + 1;
+        self.frames.insert(index, Frame { layers, delay });
+        self.select_frame(index);
+    }
+
+    /// Deletes the active frame, keeping at least one.
+    pub fn delete_frame(&mut self) {
+        let count = self.frames.len();
+        if count <= 1 {
+            return;
+        }
+        let index = self.active_frame;
+        let next = if index + 1 < count {
+            index + 1
+        } else {
+            index - 1
+        };
+        self.select_frame(next);
+        self.frames.remove(index);
+        if next > index {
+            self.active_frame = next - 1;
+        }
+    }
+
+    /// Swaps the primary and secondary colors.
+    pub fn swap_colors(&mut self) {
+        std::mem::swap(&mut self.paintbrush, &mut self.secondary);
+    }
+
+    /// Replaces `from` with `to` in every frame, layer and size, and in both
+    /// brushes.
+    pub fn recolor(&mut self, from: u8, to: u8) {
+        let grids = self
+            .layer_stacks()
+            .flat_map(|layers| layers.iter_mut())
+            .flat_map(|layer| layer.canvases.values_mut());
+        for cell in grids.flatten() {
+            if *cell == Some(from) {
+                *cell = Some(to);
+            }
+        }
+        for brush in [&mut self.paintbrush, &mut self.secondary] {
+            if *brush == Some(from) {
+                *brush = Some(to);
+            }
+        }
+    }
+
+    /// Replaces the palette with `colors` (up to the palette bar's limit)
+    /// and selects the first one.
+    pub fn load_palette(&mut self, colors: &[u8]) {
+        self.palette_colors = fit_palette(colors.iter().copied().map(Some).collect());
+        self.palette_index = 0;
+        self.paintbrush = self.palette_colors[0];
+    }
+
+    /// Fills the palette with the colors used in any frame or size.
+    pub fn extract_palette(&mut self, order: PaletteOrder) {
+        let mut colors: Vec<u8> = color_usage(&self.all_variants())
+            .into_iter()
+            .map(|(code, _)| code)
+            .collect();
+        if order == PaletteOrder::Hue {
+            colors.sort_by(|&a, &b| {
+                let (a, b) = (hue_key(a), hue_key(b));
+                a.0.cmp(&b.0).then(a.1.total_cmp(&b.1))
+            });
+        }
+        self.load_palette(&colors);
+    }
+
+    /// Palette colors not used in any frame or size.
+    pub fn unused_palette_colors(&self) -> Vec<u8> {
+        let used = color_usage(&self.all_variants());
+        self.palette_colors
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|&code| !used.iter().any(|&(c, _)| c == code))
+            .collect()
+    }
+
+    /// Drops palette colors not used in any frame or size, along with empty
+    /// slots, and selects the first slot.
+    pub fn purge_palette(&mut self) {
+        let unused = self.unused_palette_colors();
+        let kept: Vec<u8> = self
+            .palette_colors
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|code| !unused.contains(code))
+            .collect();
+        self.load_palette(&kept);
+    }
+
+    /// Adds an empty palette slot after the selected one and selects it.
+    pub fn add_palette_slot(&mut self) {
+        if self.palette_colors.len() >= MAX_PALETTE_SLOTS {
+            return;
+        }
+        let index = (self.palette_index + 1).min(self.palette_colors.len());
+        self.palette_colors.insert(index, None);
+        self.palette_index = index;
+    }
+
+    /// Removes the selected palette slot, keeping at least one.
+    pub fn remove_palette_slot(&mut self) {
+        if self.palette_colors.len() <= 1 {
+            return;
+        }
+        let index = self.palette_index.min(self.palette_colors.len() - 1);
+        self.palette_colors.remove(index);
+        self.palette_index = index.min(self.palette_colors.len() - 1);
+    }
+
+    /// Adds a blank layer above the active one, in every frame, and makes it
+    /// active.
+    pub fn add_layer(&mut self) {
+        if self.layers.len() >= MAX_LAYERS {
+            return;
+        }
+        let number = (1..)
+            .find(|n| !self.layers.iter().any(|l| l.name == format!("Layer {}", n)))
+            .unwrap_or(1);
+        let name = format!("Layer {}", number);
+        let index = (self.active_layer + 1).min(self.layers.len());
+        for layers in self.layer_stacks() {
+            layers.insert(index.min(layers.len()), Layer::new(name.clone()));
+        }
+        self.active_layer = index;
+    }
+
+    /// Deletes the active layer from every frame, keeping at least one.
+    pub fn delete_layer(&mut self) {
+        if self.layers.len() <= 1 {
+            return;
+        }
+        let index = self.active_layer;
+        for layers in self.layer_stacks() {
+            if index < layers.len() {
+                layers.remove(index);
+            }
+        }
+        self.active_layer = index.saturating_sub(1);
+    }
+
+    /// Moves the active layer one step up (towards the top) or down.
+    pub fn move_layer(&mut self, up: bool) {
+        let index = self.active_layer;
+        let target = if up { index + 1 } else { index.wrapping_sub(1) };
+        if target >= self.layers.len() {
+            return;
+        }
+        for layers in self.layer_stacks() {
+            if index.max(target) < layers.len() {
+                layers.swap(index, target);
+            }
+        }
+        self.active_layer = target;
+    }
+
+    /// Shows or hides layer `index` in every frame.
+    pub fn toggle_layer(&mut self, index: usize) {
+        let Some(visible) = self.layers.get(index).map(|layer| !layer.visible) else {
+            return;
+        };
+        for layers in self.layer_stacks() {
+            if let Some(layer) = layers.get_mut(index) {
+                layer.visible = visible;
+            }
+        }
+    }
+
+    /// Merges the active layer into the one below it, in every frame.
+    pub fn merge_layer_down(&mut self) {
+        let index = self.active_layer;
+        if index == 0 || index >= self.layers.len() {
+            return;
+        }
+        for layers in self.layer_stacks() {
+            merge_down(layers, index);
+        }
+        self.active_layer = index - 1;
+    }
+
+    /// Opens the imported document, on its first frame and layer.
+    pub fn load(&mut self, payload: ImportPayload) {
+        self.file_path = payload.path;
+        self.set_size(payload.size);
+        self.palette_colors = fit_palette(payload.palette);
+        self.paintbrush = self.palette_colors[0];
+        self.palette_index = 0;
+        self.picker_mode = false;
+        self.candidate = None;
+        let mut frames = payload.frames;
+        self.layers = std::mem::take(&mut frames[0].layers);
+        self.frames = frames;
+        self.active_frame = 0;
+        self.active_layer = 0;
+        self.playing = None;
+        self.stamp = None;
+        self.export = payload.export.unwrap_or_default();
+    }
+
+    /// Switches the active size, resetting the viewport pan and cursor.
+    pub fn set_size(&mut self, size: u8) {
+        self.size = size;
+        self.stamp_font = StampFont::for_size(size);
+        self.pan_x = 0;
+        self.pan_y = 0;
+        self.cursor = (0, 0);
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct State {
     pub flow: FlowState,
@@ -55,23 +969,68 @@ impl Default for State {
             flow: FlowState {
                 phase: AppPhase::Splash,
                 viewport_too_small: false,
+                spare: (0, 0),
                 launch_start_new: false,
                 launch_import_started: false,
+                launch_template: 0,
+                gallery_dir: ".".to_string(),
+                gallery: vec![],
+                gallery_index: 0,
+                gallery_return: AppPhase::Launch,
+                gallery_open: false,
+                recent: vec![],
+                recent_open: None,
+                autosaved_ms: 0.0,
+                autosaved: String::new(),
+                recovery: None,
+                recovery_return: AppPhase::Launch,
+                recovery_restore: None,
+                settings_index: 0,
+                settings_editing: false,
+                settings_return: AppPhase::Launch,
                 splash_started_ms: None,
                 message_text: None,
                 message_color: 196,
+                status: None,
+                status_seen: None,
+                status_ms: 0.0,
+                confirm: None,
                 exit_flow: ExitFlow::None,
             },
             editor: EditorState {
                 candidate: None,
                 paintbrush: None,
+                secondary: None,
                 prev_color_on_canvas: None,
                 palette_index: 0,
-                palette_colors: vec![None; 8],
+                palette_colors: vec![None; DEFAULT_PALETTE_SLOTS],
+                linked_palette: false,
+                palette_format: PaletteFormat::Gpl,
+                palette_drag: None,
                 picker_mode: false,
-                canvas16_data: vec![None; 16 * 16],
-                canvas8_data: vec![None; 8 * 8],
+                layers: vec![Layer::new("Layer 1".to_string())],
+                active_layer: 0,
+                frames: vec![Frame::new(vec![])],
+                active_frame: 0,
+                onion_skin: false,
+                playing: None,
+                export_layers: config().export_layers,
+                export: ExportOptions::new(),
                 size: 8,
+                pan_x: 0,
+                pan_y: 0,
+                zoom: None,
+                panel: Panel::Colors,
+                view: ViewOptions::default(),
+                vision: Vision::Normal,
+                fill: FillOptions::default(),
+                snap_dither: false,
+                stamp: None,
+                stamp_font: StampFont::Small,
+                hover: None,
+                focus: None,
+                focus_action: 0,
+                cursor: (0, 0),
                 save_flag: false,
                 save_requested: false,
                 file_path: String::new(),
@@ -81,4 +1040,7 @@ impl Default for State {
     }
 }
 
-pub const MIN_SPLASH_MS: f64 = 2000.0;
+/// Time between autosaves of the document to the recovery file.
+pub const AUTOSAVE_MS: f64 = 30000.0;
+/// Time a status note stays up.
+pub const STATUS_MS: f64 = 4000.0;
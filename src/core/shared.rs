@@ -1,6 +1,43 @@
+use crate::core::{
+    config::config,
+    model::{DEFAULT_PALETTE_SLOTS, ExportOptions, Frame, Layer},
+};
 use std::sync::{Arc, LazyLock, Mutex};
 
-pub type ImportPayload = (Vec<Option<u8>>, Vec<Option<u8>>, u8, String);
+/// An imported icon document: its frames (each with layers holding every size
+/// variant), the palette, the active size and the (possibly rewritten) file
+/// path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportPayload {
+    pub frames: Vec<Frame>,
+    pub palette: Vec<Option<u8>>,
+    pub size: u8,
+    pub path: String,
+    /// Export settings saved with the document, if any.
+    pub export: Option<ExportOptions>,
+    /// A shared session (see `features::session`) restoring the editor as it
+    /// was, tool settings included, over the document.
+    pub session: Option<String>,
+}
+
+impl ImportPayload {
+    /// A blank document (one frame of one layer, all variants empty) of the
+    /// configured size and palette.
+    pub fn blank(path: String) -> Self {
+        let config = config();
+        let mut palette: Vec<Option<u8>> = config.palette.iter().copied().map(Some).collect();
+        palette.resize(palette.len().max(DEFAULT_PALETTE_SLOTS), None);
+        ImportPayload {
+            frames: vec![Frame::new(vec![Layer::new("Layer 1".to_string())])],
+            palette,
+            size: config.size,
+            path,
+            export: None,
+            session: None,
+        }
+    }
+}
+
 pub type ImportOutcome = Result<ImportPayload, String>;
 
 // Shared between bootstrap orchestration and splash footer.
@@ -0,0 +1,216 @@
+use crate::core::{color::canvas_to_rgba, model::Frame};
+use image::{
+    Delay, RgbaImage,
+    codecs::gif::{GifEncoder, Repeat},
+};
+use incredible::*;
+use std::path::Path;
+
+/// Every frame of the given size flattened to RGBA.
+fn frame_pixels(frames: &[Frame], size: u8) -> Vec<Vec<u8>> {
+    frames
+        .iter()
+        .map(|frame| canvas_to_rgba(&frame.composite(size)))
+        .collect()
+}
+
+/// Builds a looping animated GIF of the given size, at its native size.
+pub fn build_gif(frames: &[Frame], size: u8) -> Result<Vec<u8>, String> {
+    let side = size as u32;
+    let gif_frames = frame_pixels(frames, size)
+        .into_iter()
+        .zip(frames)
+        .map(|(pixels, frame)| {
+            let buffer = RgbaImage::from_raw(side, side, pixels)
+                .ok_or("Failed to build GIF frame.".to_string())?;
+            let delay = Delay::from_numer_denom_ms(frame.delay as u32, 1);
+            Ok(image::Frame::from_parts(buffer, 0, 0, delay))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut out = Vec::new();
+    {
+        let mut encoder = GifEncoder::new(&mut out);
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .and_then(|_| encoder.encode_frames(gif_frames))
+            .map_err(|e| format!("Failed to build GIF: {}", e))?;
+    }
+    Ok(out)
+}
+
+/// Builds a looping animated PNG of the given size, at its native size.
+pub fn build_apng(frames: &[Frame], size: u8) -> Result<Vec<u8>, String> {
+    let error = |e: png::EncodingError| format!("Failed to build APNG: {}", e);
+    let side = size as u32;
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, side, side);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(frames.len() as u32, 0)
+        .map_err(error)?;
+
+    let mut writer = encoder.write_header().map_err(error)?;
+    for (pixels, frame) in frame_pixels(frames, size).into_iter().zip(frames) {
+        writer.set_frame_delay(frame.delay, 1000).map_err(error)?;
+        writer
+            .set_dispose_op(png::DisposeOp::Background)
+            .map_err(error)?;
+        writer.write_image_data(&pixels).map_err(error)?;
+    }
+    writer.finish().map_err(error)?;
+    Ok(out)
+}
+
+/// Builds an SVG that plays the frames with SMIL: one group per frame, shown
+/// only during its own slice of the loop.
+pub fn build_animated_svg(frames: &[Frame], size: u8, px: usize) -> String {
+    let side = size as usize * px;
+    let total: u32 = frames
+        .iter()
+        .map(|frame| frame.delay as u32)
+        .sum::<u32>()
+        .max(1);
+
+    let mut out = String::with_capacity(4096);
+    out.push_str(&format!(
+        "<svg version=\"1.1\" baseProfile=\"full\" width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">\n",
+        side, side
+    ));
+
+    let mut start = 0;
+    for frame in frames {
+        let end = start + frame.delay as u32;
+        out.push_str("<g visibility=\"hidden\">\n");
+        out.push_str(&format!(
+            "<animate attributeName=\"visibility\" calcMode=\"discrete\" values=\"hidden;visible;hidden\" keyTimes=\"0;{:.4};{:.4}\" dur=\"{}ms\" repeatCount=\"indefinite\" />\n",
+            start as f64 / total as f64,
+            end as f64 / total as f64,
+            total
+        ));
+        for (i, cell) in frame.composite(size).iter().enumerate() {
+            if let Some(idx) = cell {
+                out.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" />\n",
+                    (i % size as usize) * px,
+                    (i / size as usize) * px,
+                    px,
+                    px,
+                    Colors::ansi8_to_hex(*idx)
+                ));
+            }
+        }
+        out.push_str("</g>\n");
+        start = end;
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// The animation saved next to the icon at `svg_path`: (path, bytes) of
+/// `<name>-animated.gif`, `.png` (APNG) and `.svg` (SMIL). The suffix keeps a
+/// source image the icon was imported from from being overwritten. The SVG
+/// uses `px` per icon pixel. It takes more than one frame.
+pub fn animation_files(
+    frames: &[Frame],
+    size: u8,
+    px: usize,
+    svg_path: &str,
+) -> Result<Vec<(String, Vec<u8>)>, String> {
+    if frames.len() < 2 {
+        return Err("Add a second frame for an animation".to_string());
+    }
+
+    let target = Path::new(svg_path);
+    let stem = target
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("icon");
+    let animated = |ext: &str| {
+        target
+            .with_file_name(format!("{}-animated.{}", stem, ext))
+            .to_string_lossy()
+            .into_owned()
+    };
+
+    let svg = build_animated_svg(frames, size, px);
+    Ok(vec![
+        (animated("gif"), build_gif(frames, size)?),
+        (animated("png"), build_apng(frames, size)?),
+        (animated("svg"), svg.into_bytes()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::model::{FRAME_DELAYS, Layer};
+    use image::{AnimationDecoder, codecs::gif::GifDecoder};
+    use std::{io::Cursor, time::Duration};
+
+    /// Three frames, each with one red pixel further along the top row.
+    fn frames() -> Vec<Frame> {
+        (0..3)
+            .map(|n| {
+                let mut layer = Layer::new("Layer 1".to_string());
+                layer.canvases.get_mut(&8).unwrap()[n] = Some(196);
+                let mut frame = Frame::new(vec![layer]);
+                frame.delay = FRAME_DELAYS[n];
+                frame
+            })
+            .collect()
+    }
+
+    #[test]
+    fn gif_holds_every_frame() {
+        let gif = build_gif(&frames(), 8).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+
+        let decoded = GifDecoder::new(Cursor::new(gif))
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap();
+        assert_eq!(decoded.len(), 3);
+        for (n, (frame, source)) in decoded.iter().zip(frames()).enumerate() {
+            let delay = Duration::from(frame.delay());
+            assert_eq!(delay, Duration::from_millis(source.delay as u64));
+            assert_eq!(frame.buffer().dimensions(), (8, 8));
+            assert_eq!(frame.buffer().get_pixel(n as u32, 0).0, [255, 0, 0, 255]);
+        }
+    }
+
+    #[test]
+    fn apng_holds_every_frame() {
+        let apng = build_apng(&frames(), 8).unwrap();
+        assert!(apng.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let mut reader = png::Decoder::new(Cursor::new(apng)).read_info().unwrap();
+        let control = reader.info().animation_control.unwrap();
+        assert_eq!((control.num_frames, control.num_plays), (3, 0));
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        for source in frames() {
+            reader.next_frame(&mut buffer).unwrap();
+            let frame = reader.info().frame_control.unwrap();
+            assert_eq!((frame.delay_num, frame.delay_den), (source.delay, 1000));
+        }
+    }
+
+    #[test]
+    fn animation_files_sit_next_to_the_icon() {
+        let files = animation_files(&frames(), 8, 4, "icons/logo.svg").unwrap();
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "icons/logo-animated.gif",
+                "icons/logo-animated.png",
+                "icons/logo-animated.svg"
+            ]
+        );
+        assert!(animation_files(&frames()[..1], 8, 4, "icons/logo.svg").is_err());
+    }
+}
@@ -1,7 +1,14 @@
+use crate::core::{
+    color::canvas_to_rgba,
+    model::{EditorState, ExportOptions, Frame, Layer},
+};
+use image::{
+    ExtendedColorType,
+    codecs::ico::{IcoEncoder, IcoFrame},
+};
 use incredible::*;
 #[cfg(not(target_arch = "wasm32"))]
 use std::fs;
-#[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
 
 fn to_colon_list(values: &[Option<u8>]) -> String {
@@ -15,36 +22,207 @@ fn to_colon_list(values: &[Option<u8>]) -> String {
         .join(":")
 }
 
-pub fn build_svg(
+/// Side length of a square canvas.
+fn canvas_side(data: &[Option<u8>]) -> usize {
+    (data.len() as f64).sqrt() as usize
+}
+
+/// Size variants worth exporting: the ones with at least one painted cell.
+pub fn drawn_variants<V: AsRef<[Option<u8>]>>(variants: &[V]) -> Vec<&[Option<u8>]> {
+    variants
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|data| data.iter().any(|cell| cell.is_some()))
+        .collect()
+}
+
+/// Escapes text for an XML attribute value.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn rect(i: usize, cols: usize, px: usize, fill: &str) -> String {
+    format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" />\n",
+        (i % cols) * px,
+        (i / cols) * px,
+        px,
+        px,
+        fill
+    )
+}
+
+/// One `<g>` per layer holding its painted cells, named so the layers can be
+/// read back, plus every drawn size of each layer as `rusticon-layer-<n>-<size>`
+/// metadata.
+fn layer_groups(layers: &[Layer], cols: usize, px: usize) -> (String, String) {
+    let (mut groups, mut meta) = (String::new(), String::new());
+    for (n, layer) in layers.iter().enumerate() {
+        groups.push_str(&format!(
+            "<g data-rusticon-layer=\"{}\" data-name=\"{}\"{}>\n",
+            n,
+            xml_escape(&layer.name),
+            if layer.visible {
+                ""
+            } else {
+                " display=\"none\""
+            }
+        ));
+        let data = layer.canvas(cols as u8);
+        for (i, cell) in data.iter().enumerate() {
+            if let Some(idx) = cell {
+                groups.push_str(&rect(i, cols, px, &Colors::ansi8_to_hex(*idx)));
+            }
+        }
+        groups.push_str("</g>\n");
+
+        for (size, grid) in &layer.canvases {
+            if grid.iter().any(|cell| cell.is_some()) {
+                meta.push_str(&format!(
+                    "<!-- rusticon-layer-{}-{}:{}rusticon-layer-{}-{} -->\n",
+                    n,
+                    size,
+                    to_colon_list(grid),
+                    n,
+                    size
+                ));
+            }
+        }
+    }
+    (groups, meta)
+}
+
+/// Export options as `rusticon-export`: profile, pixel size, viewBox flag,
+/// background, corner radius and padding.
+fn export_meta(options: &ExportOptions) -> String {
+    format!(
+        "<!-- rusticon-export:{}:{}:{}:{}:{}:{}rusticon-export -->\n",
+        options.profile.label(),
+        options.px,
+        options.view_box as u8,
+        to_colon_list(&[options.background]),
+        options.radius,
+        options.padding
+    )
+}
+
+/// Frame delays as `rusticon-frames`, then every drawn size of every layer
+/// of the frames after the first as `rusticon-frame-<f>-<n>-<size>`.
+fn frame_meta(frames: &[Frame]) -> String {
+    let delays = frames
+        .iter()
+        .map(|frame| frame.delay.to_string())
+        .collect::<Vec<_>>()
+        .join(":");
+    let mut meta = format!("<!-- rusticon-frames:{}rusticon-frames -->\n", delays);
+
+    for (f, frame) in frames.iter().enumerate().skip(1) {
+        for (n, layer) in frame.layers.iter().enumerate() {
+            for (size, grid) in &layer.canvases {
+                if grid.iter().any(|cell| cell.is_some()) {
+                    meta.push_str(&format!(
+                        "<!-- rusticon-frame-{}-{}-{}:{}rusticon-frame-{}-{}-{} -->\n",
+                        f,
+                        n,
+                        size,
+                        to_colon_list(grid),
+                        f,
+                        n,
+                        size
+                    ));
+                }
+            }
+        }
+    }
+    meta
+}
+
+/// Builds a Crumbicon SVG for `data`, also recording the other drawn size
+/// variants in `variants` as `rusticon-data-<size>` metadata so the document
+/// can be re-imported with all of them. The SVG shows the first of `frames`;
+/// with more than one layer it is kept as one group per layer instead of
+/// flattened, and any further frames are kept as metadata. `options` sets
+/// the pixel size (or a unitless viewBox), background, rounded corners and
+/// padding, and is kept as metadata too.
+pub fn build_svg<V: AsRef<[Option<u8>]>>(
     data: &[Option<u8>],
     palette: &[Option<u8>],
+    variants: &[V],
+    frames: &[Frame],
     rows: usize,
     cols: usize,
-    px: usize,
+    options: &ExportOptions,
 ) -> String {
-    let width = cols * px;
-    let height = rows * px;
+    let px = if options.view_box { 1 } else { options.px };
+    let pad = options.padding as usize;
+    let width = (cols + 2 * pad) * px;
+    let height = (rows + 2 * pad) * px;
 
     let mut out = String::with_capacity(4096);
-    out.push_str(&format!(
-        "<svg version=\"1.1\" baseProfile=\"full\" width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">\n",
-        width, height
-    ));
-
-    for (i, cell) in data.iter().enumerate() {
-        let x = (i % cols) * px;
-        let y = (i / cols) * px;
-
-        let fill = match cell {
-            Some(idx) => Colors::ansi8_to_hex(*idx),
-            None => "none".to_string(),
-        };
+    if options.view_box {
+        out.push_str(&format!(
+            "<svg version=\"1.1\" baseProfile=\"full\" viewBox=\"0 0 {} {}\" xmlns=\"http://www.w3.org/2000/svg\">\n",
+            width, height
+        ));
+    } else {
+        out.push_str(&format!(
+            "<svg version=\"1.1\" baseProfile=\"full\" width=\"{}\" height=\"{}\" xmlns=\"http://www.w3.org/2000/svg\">\n",
+            width, height
+        ));
+    }
 
+    // Wrappers opened here are closed in reverse after the drawing.
+    let mut close = String::new();
+    if options.radius > 0 {
+        let r = width.min(height) as f64 * options.radius as f64 / 100.0;
+        out.push_str(&format!(
+            "<defs><clipPath id=\"rusticon-mask\"><rect width=\"{}\" height=\"{}\" rx=\"{}\" /></clipPath></defs>\n<g clip-path=\"url(#rusticon-mask)\">\n",
+            width, height, r
+        ));
+        close.push_str("</g>\n");
+    }
+    if let Some(code) = options.background {
         out.push_str(&format!(
-            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" />\n",
-            x, y, px, px, fill
+            "<rect width=\"{}\" height=\"{}\" fill=\"{}\" />\n",
+            width,
+            height,
+            Colors::ansi8_to_hex(code)
         ));
     }
+    if pad > 0 {
+        out.push_str(&format!(
+            "<g transform=\"translate({} {})\">\n",
+            pad * px,
+            pad * px
+        ));
+        close.insert_str(0, "</g>\n");
+    }
+
+    let layers = frames
+        .first()
+        .map(|frame| frame.layers.as_slice())
+        .unwrap_or(&[]);
+    let (groups, layer_meta) = if layers.len() > 1 {
+        layer_groups(layers, cols, px)
+    } else {
+        (String::new(), String::new())
+    };
+
+    if groups.is_empty() {
+        for (i, cell) in data.iter().enumerate() {
+            let fill = match cell {
+                Some(idx) => Colors::ansi8_to_hex(*idx),
+                None => "none".to_string(),
+            };
+            out.push_str(&rect(i, cols, px, &fill));
+        }
+    } else {
+        out.push_str(&groups);
+    }
+    out.push_str(&close);
 
     out.push_str("\n</svg>\n");
 
@@ -55,20 +233,114 @@ pub fn build_svg(
         data_str, palette_str
     ));
 
+    for variant in drawn_variants(variants) {
+        let side = canvas_side(variant);
+        if side == cols {
+            continue;
+        }
+        out.push_str(&format!(
+            "<!-- rusticon-data-{}:{}rusticon-data-{} -->\n",
+            side,
+            to_colon_list(variant),
+            side
+        ));
+    }
+    out.push_str(&layer_meta);
+    if frames.len() > 1 {
+        out.push_str(&frame_meta(frames));
+    }
+    out.push_str(&export_meta(options));
+
     out
 }
 
-/// Export a Crumbicon SVG to a file (native target only).
+/// The editor's document as saved: every frame (flattened unless layers are
+/// kept), the palette and every size, showing the active size with the
+/// document's export options.
+pub fn document_svg(editor: &EditorState) -> String {
+    let size = editor.size as usize;
+    let frames = editor.document_frames(!editor.export_layers);
+    build_svg(
+        &frames[0].composite(editor.size),
+        &editor.palette_colors,
+        &frames[0].variants(),
+        &frames,
+        size,
+        size,
+        &editor.export,
+    )
+}
+
+/// Builds an ICO holding one image per size variant, at its native size.
+pub fn build_ico(variants: &[&[Option<u8>]]) -> Result<Vec<u8>, String> {
+    let rgba: Vec<(u32, Vec<u8>)> = variants
+        .iter()
+        .map(|data| (canvas_side(data) as u32, canvas_to_rgba(data)))
+        .collect();
+
+    let frames = rgba
+        .iter()
+        .map(|(side, pixels)| IcoFrame::as_png(pixels, *side, *side, ExtendedColorType::Rgba8))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to build ICO: {}", e))?;
+
+    let mut out = Vec::new();
+    IcoEncoder::new(&mut out)
+        .encode_images(&frames)
+        .map_err(|e| format!("Failed to build ICO: {}", e))?;
+    Ok(out)
+}
+
+/// Builds the HTML `<link>` snippet for an exported icon set, with `sizes`
+/// hints for every variant held in the ICO.
+pub fn build_html_snippet(svg_name: &str, ico_name: &str, variants: &[&[Option<u8>]]) -> String {
+    let sizes = variants
+        .iter()
+        .map(|data| {
+            let side = canvas_side(data);
+            format!("{}x{}", side, side)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(
+        "<link rel=\"icon\" href=\"{}\" sizes=\"{}\">\n<link rel=\"icon\" href=\"{}\" type=\"image/svg+xml\" sizes=\"any\">\n",
+        ico_name, sizes, svg_name
+    )
+}
+
+/// The ICO holding every drawn size and the HTML snippet linking it and the
+/// SVG, named after the SVG at `svg_path`: (path, bytes). It takes more than
+/// one drawn size.
+pub fn icon_set_files<V: AsRef<[Option<u8>]>>(
+    variants: &[V],
+    svg_path: &str,
+) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let drawn = drawn_variants(variants);
+    if drawn.len() < 2 {
+        return Err("Draw a second size for an icon set".to_string());
+    }
+
+    let svg_path = Path::new(svg_path);
+    let ico_path = svg_path.with_extension("ico");
+    let html_path = svg_path.with_extension("html");
+    let file_name = |path: &Path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+    let path = |path: &Path| path.to_string_lossy().into_owned();
+
+    let ico = build_ico(&drawn)?;
+    let html = build_html_snippet(&file_name(svg_path), &file_name(&ico_path), &drawn);
+    Ok(vec![
+        (path(&ico_path), ico),
+        (path(&html_path), html.into_bytes()),
+    ])
+}
+
+/// Writes an SVG built by `build_svg` to a file (native target only).
 #[cfg(not(target_arch = "wasm32"))]
-pub fn export_svg<P: AsRef<Path>>(
-    data: &[Option<u8>],
-    palette: &[Option<u8>],
-    rows: usize,
-    cols: usize,
-    px: usize,
-    target: P,
-) -> Result<(), String> {
-    let out = build_svg(data, palette, rows, cols, px);
-    fs::write(target, out).map_err(|e| format!("Failed to write SVG: {}", e))?;
-    Ok(())
+pub fn export_svg<P: AsRef<Path>>(svg: &str, target: P) -> Result<(), String> {
+    fs::write(target, svg).map_err(|e| format!("Failed to write SVG: {}", e))
 }
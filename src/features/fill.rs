@@ -0,0 +1,190 @@
+use crate::core::{
+    color::{blend, color_distance},
+    model::FillOptions,
+};
+
+/// Whether `cell` counts as the fill `target` color.
+fn matches(target: Option<u8>, cell: Option<u8>, tolerance: u8) -> bool {
+    match (target, cell) {
+        (None, None) => true,
+        (Some(a), Some(b)) => a == b || color_distance(a, b) <= tolerance as f32,
+        _ => false,
+    }
+}
+
+/// Indices of the cells a fill starting at (row, col) reaches.
+pub fn fill_region(
+    data: &[Option<u8>],
+    size: usize,
+    start: (usize, usize),
+    options: &FillOptions,
+) -> Vec<usize> {
+    let (row, col) = start;
+    if row >= size || col >= size {
+        return vec![];
+    }
+    let target = data[row * size + col];
+
+    if options.global {
+        return (0..data.len())
+            .filter(|&idx| matches(target, data[idx], options.tolerance))
+            .collect();
+    }
+
+    let mut visited = vec![false; data.len()];
+    let mut region = vec![];
+    let mut stack = vec![(row, col)];
+
+    while let Some((r, c)) = stack.pop() {
+        let idx = r * size + c;
+        if visited[idx] || !matches(target, data[idx], options.tolerance) {
+            continue;
+        }
+        visited[idx] = true;
+        region.push(idx);
+
+        for dr in -1isize..=1 {
+            for dc in -1isize..=1 {
+                let diagonal = dr != 0 && dc != 0;
+                if (dr == 0 && dc == 0) || (diagonal && !options.connectivity8) {
+                    continue;
+                }
+                let (nr, nc) = (r as isize + dr, c as isize + dc);
+                if nr >= 0 && nc >= 0 && (nr as usize) < size && (nc as usize) < size {
+                    stack.push((nr as usize, nc as usize));
+                }
+            }
+        }
+    }
+
+    region
+}
+
+/// Fills the region at `start` with `colors.0`, or with a gradient from
+/// `colors.0` to `colors.1` along the region's longer side when the gradient
+/// option is on and both colors are opaque.
+pub fn fill(
+    data: &mut [Option<u8>],
+    size: usize,
+    start: (usize, usize),
+    colors: (Option<u8>, Option<u8>),
+    options: &FillOptions,
+) {
+    let region = fill_region(data, size, start, options);
+
+    let (Some(from), Some(to), true) = (colors.0, colors.1, options.gradient) else {
+        for idx in region {
+            data[idx] = colors.0;
+        }
+        return;
+    };
+
+    let rows = region.iter().map(|idx| idx / size);
+    let cols = region.iter().map(|idx| idx % size);
+    let (min_row, max_row) = (rows.clone().min().unwrap_or(0), rows.max().unwrap_or(0));
+    let (min_col, max_col) = (cols.clone().min().unwrap_or(0), cols.max().unwrap_or(0));
+    let horizontal = max_col - min_col >= max_row - min_row;
+
+    for idx in region {
+        let (pos, min, max) = if horizontal {
+            (idx % size, min_col, max_col)
+        } else {
+            (idx / size, min_row, max_row)
+        };
+        let t = if max > min {
+            (pos - min) as f32 / (max - min) as f32
+        } else {
+            0.0
+        };
+        data[idx] = Some(blend(from, to, t));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const O: Option<u8> = None;
+    const R: Option<u8> = Some(196);
+
+    /// A 4x4 grid: a red ring with a gap at the bottom-right, and a red cell
+    /// touching the inside only at a corner.
+    fn grid() -> Vec<Option<u8>> {
+        vec![
+            R, R, R, O, //
+            R, O, R, O, //
+            R, R, O, R, //
+            O, O, O, O, //
+        ]
+    }
+
+    #[test]
+    fn four_way_fill_stays_in_the_ring() {
+        let options = FillOptions::default();
+        assert_eq!(fill_region(&grid(), 4, (1, 1), &options), vec![5]);
+    }
+
+    #[test]
+    fn eight_way_fill_leaks_through_corners() {
+        let options = FillOptions {
+            connectivity8: true,
+            ..FillOptions::default()
+        };
+        let mut region = fill_region(&grid(), 4, (1, 1), &options);
+        region.sort_unstable();
+        assert_eq!(region, vec![3, 5, 7, 10, 12, 13, 14, 15]);
+    }
+
+    #[test]
+    fn global_fill_takes_unconnected_cells() {
+        let options = FillOptions {
+            global: true,
+            ..FillOptions::default()
+        };
+        let mut region = fill_region(&grid(), 4, (0, 0), &options);
+        region.sort_unstable();
+        assert_eq!(region, vec![0, 1, 2, 4, 6, 8, 9, 11]);
+        assert!(fill_region(&grid(), 4, (4, 0), &options).is_empty());
+    }
+
+    #[test]
+    fn tolerance_takes_close_colors() {
+        // 196 and 160 are two reds, 21 is blue.
+        let data = vec![Some(196), Some(160), Some(21), Some(196)];
+        let exact = FillOptions::default();
+        assert_eq!(fill_region(&data, 2, (0, 0), &exact), vec![0]);
+
+        let close = FillOptions {
+            tolerance: color_distance(196, 160).ceil() as u8,
+            ..FillOptions::default()
+        };
+        let mut region = fill_region(&data, 2, (0, 0), &close);
+        region.sort_unstable();
+        assert_eq!(region, vec![0, 1, 3]);
+        assert!(color_distance(196, 21) > close.tolerance as f32);
+    }
+
+    #[test]
+    fn gradient_runs_along_the_longer_side() {
+        let mut data = vec![None; 16];
+        let options = FillOptions {
+            gradient: true,
+            ..FillOptions::default()
+        };
+        // The top row is walled off from the rest.
+        data[4..8].copy_from_slice(&[R; 4]);
+        fill(&mut data, 4, (0, 0), (Some(16), Some(231)), &options);
+
+        assert_eq!(data[0], Some(blend(16, 231, 0.0)));
+        assert_eq!(data[3], Some(blend(16, 231, 1.0)));
+        assert_eq!(data[1], Some(blend(16, 231, 1.0 / 3.0)));
+        assert_eq!(&data[4..8], &[R; 4]);
+        assert!(data[8..].iter().all(Option::is_none));
+
+        // A transparent color fills flat.
+        fill(&mut data, 4, (3, 0), (None, Some(231)), &options);
+        assert!(data[8..].iter().all(Option::is_none));
+        fill(&mut data, 4, (3, 0), (Some(21), None), &options);
+        assert!(data[8..].iter().all(|cell| *cell == Some(21)));
+    }
+}
@@ -0,0 +1,35 @@
+use crate::core::model::GalleryEntry;
+use crate::features::import::import_bytes;
+use std::path::Path;
+
+/// The Crumbicon and Rusticon SVGs among `files` (path, bytes), in the given
+/// order. Other files, including plain images, are skipped.
+fn icon_entries(files: Vec<(String, Vec<u8>)>) -> Vec<GalleryEntry> {
+    files
+        .into_iter()
+        .filter(|(path, bytes)| {
+            path.to_ascii_lowercase().ends_with(".svg")
+                && String::from_utf8_lossy(bytes).contains("<!-- crumbicon-data:")
+        })
+        .filter_map(|(path, bytes)| {
+            let payload = import_bytes(&path, &bytes).ok()?;
+            let name = Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or(path);
+            Some(GalleryEntry { name, payload })
+        })
+        .collect()
+}
+
+/// The icons among `files` for the gallery browser, sorted by name.
+pub fn gallery_entries(files: Vec<(String, Vec<u8>)>) -> Vec<GalleryEntry> {
+    let mut entries = icon_entries(files);
+    entries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    entries
+}
+
+/// The icons among the recent `files`, most recent first.
+pub fn recent_entries(files: Vec<(String, Vec<u8>)>) -> Vec<GalleryEntry> {
+    icon_entries(files)
+}
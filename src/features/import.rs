@@ -2,6 +2,17 @@ use image::{DynamicImage, GenericImageView, imageops::FilterType};
 use incredible::*;
 use std::path::Path;
 
+use crate::core::{
+    color::color_usage,
+    config::EXPORT_PIXEL_SIZES,
+    model::{
+        CANVAS_SIZES, EXPORT_PADDINGS, EXPORT_RADII, ExportOptions, ExportProfile, Frame, Layer,
+        MAX_FRAMES, MAX_LAYERS, State,
+    },
+    shared::ImportPayload,
+};
+use crate::features::session::{session_from_json, session_path};
+
 #[cfg(not(target_arch = "wasm32"))]
 use std::fs;
 
@@ -29,7 +40,151 @@ fn get_crumbicon_data(text: &str, start_needle: &str, end_needle: &str) -> Vec<O
         .collect()
 }
 
-pub fn load_and_resize_image_bytes(bytes: &[u8]) -> Result<Vec<Vec<[u8; 4]>>, String> {
+/// Extracts an extra size variant stored by Rusticon next to the Crumbicon data.
+fn get_variant_data(text: &str, size: usize) -> Option<Vec<Option<u8>>> {
+    let start = format!("<!-- rusticon-data-{}:", size);
+    let end = format!("rusticon-data-{} -->", size);
+    let data = get_crumbicon_data(text, &start, &end);
+    (data.len() == size * size).then_some(data)
+}
+
+/// Value of the XML attribute `name` inside `tag`, unescaped.
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    let needle = format!("{}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let end = start + tag[start..].find('"')?;
+    Some(
+        tag[start..end]
+            .replace("&quot;", "\"")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&"),
+    )
+}
+
+/// Reads the layers Rusticon keeps as `<g data-rusticon-layer>` groups, with
+/// each size of each layer in `rusticon-layer-<n>-<size>`. Empty when the
+/// file was saved flattened.
+fn get_layers(text: &str) -> Vec<Layer> {
+    let mut layers = vec![];
+    for n in 0..MAX_LAYERS {
+        let Some(start) = text.find(&format!("<g data-rusticon-layer=\"{}\"", n)) else {
+            break;
+        };
+        let tag = &text[start..start + text[start..].find('>').unwrap_or(0)];
+
+        let name = get_attribute(tag, "data-name").unwrap_or_else(|| format!("Layer {}", n + 1));
+        let mut layer = Layer::new(name);
+        layer.visible = !tag.contains("display=\"none\"");
+        for size in CANVAS_SIZES {
+            let start = format!("<!-- rusticon-layer-{}-{}:", n, size);
+            let end = format!("rusticon-layer-{}-{} -->", n, size);
+            let data = get_crumbicon_data(text, &start, &end);
+            if data.len() == size as usize * size as usize {
+                layer.canvases.insert(size, data);
+            }
+        }
+        layers.push(layer);
+    }
+    layers
+}
+
+/// Completes the `first` frame with the animation Rusticon keeps as
+/// `rusticon-frames` delays and `rusticon-frame-<f>-<n>-<size>` data. Later
+/// frames share the layer names and visibility of the first one. Still
+/// documents come back as the single `first` frame.
+fn get_frames(text: &str, first: Frame) -> Vec<Frame> {
+    let start_needle = "<!-- rusticon-frames:";
+    let Some(start) = text.find(start_needle).map(|pos| pos + start_needle.len()) else {
+        return vec![first];
+    };
+    let Some(end) = text.find("rusticon-frames -->") else {
+        return vec![first];
+    };
+    let delays: Vec<u16> = text[start..end]
+        .split(':')
+        .filter_map(|delay| delay.trim().parse().ok())
+        .take(MAX_FRAMES)
+        .collect();
+
+    let mut frames = vec![first];
+    if let Some(&delay) = delays.first() {
+        frames[0].delay = delay;
+    }
+    for (f, &delay) in delays.iter().enumerate().skip(1) {
+        let mut frame = Frame::new(vec![]);
+        frame.delay = delay;
+        for (n, base) in frames[0].layers.iter().enumerate() {
+            let mut layer = Layer::new(base.name.clone());
+            layer.visible = base.visible;
+            for size in CANVAS_SIZES {
+                let start = format!("<!-- rusticon-frame-{}-{}-{}:", f, n, size);
+                let end = format!("rusticon-frame-{}-{}-{} -->", f, n, size);
+                let data = get_crumbicon_data(text, &start, &end);
+                if data.len() == size as usize * size as usize {
+                    layer.canvases.insert(size, data);
+                }
+            }
+            frame.layers.push(layer);
+        }
+        frames.push(frame);
+    }
+    frames
+}
+
+/// Reads the export options Rusticon keeps as `rusticon-export`. `None` when
+/// missing or out of range, so the defaults apply.
+fn get_export(text: &str) -> Option<ExportOptions> {
+    let start_needle = "<!-- rusticon-export:";
+    let start = text.find(start_needle)? + start_needle.len();
+    let end = text.find("rusticon-export -->")?;
+    let fields: Vec<&str> = text.get(start..end)?.split(':').map(str::trim).collect();
+    let [profile, px, view_box, background, radius, padding] = fields[..] else {
+        return None;
+    };
+
+    let profile = ExportProfile::ALL
+        .into_iter()
+        .find(|p| p.label() == profile)?;
+    let background = match background {
+        "void" => None,
+        code => Some(code.parse::<u8>().ok()?),
+    };
+    Some(ExportOptions {
+        profile,
+        px: px
+            .parse()
+            .ok()
+            .filter(|px| EXPORT_PIXEL_SIZES.contains(px))?,
+        view_box: view_box == "1",
+        background,
+        radius: radius.parse().ok().filter(|r| EXPORT_RADII.contains(r))?,
+        padding: padding
+            .parse()
+            .ok()
+            .filter(|p| EXPORT_PADDINGS.contains(p))?,
+    })
+}
+
+/// Converts a 2D RGBA grid to flattened ANSI8 data.
+fn pixels_to_data(pixels_2d: &[Vec<[u8; 4]>]) -> Vec<Option<u8>> {
+    let mut data = Vec::with_capacity(pixels_2d.len() * pixels_2d.len());
+    for row in pixels_2d {
+        for &px in row {
+            let a = px[3]; // alpha channel
+            let val = if a == 0 {
+                None // transparent
+            } else {
+                let ansi8 = Colors::rgb_to_ansi8([px[0], px[1], px[2]]); // RGB → ANSI8
+                Some(ansi8)
+            };
+            data.push(val);
+        }
+    }
+    data
+}
+
+pub fn load_and_resize_image_bytes(bytes: &[u8], target: u32) -> Result<Vec<Vec<[u8; 4]>>, String> {
     // Guess image format from bytes first; fall back to the image crate detector.
     let img = match image::guess_format(bytes) {
         Ok(format) => image::load_from_memory_with_format(bytes, format)
@@ -47,14 +202,15 @@ pub fn load_and_resize_image_bytes(bytes: &[u8]) -> Result<Vec<Vec<[u8; 4]>>, St
     // Crop to center square
     let cropped = img.crop_imm(x, y, size, size);
 
-    // Resize to 16x16 (forcing size)
-    let resized: DynamicImage = cropped.resize_exact(16, 16, FilterType::Nearest);
+    // Resize to target (forcing size)
+    let resized: DynamicImage = cropped.resize_exact(target, target, FilterType::Nearest);
 
     // Convert to 2D vector of RGBA
-    let mut pixels_2d = vec![vec![[0u8; 4]; 16]; 16];
-    for y in 0..16 {
-        for x in 0..16 {
-            pixels_2d[y][x] = resized
+    let side = target as usize;
+    let mut pixels_2d = vec![vec![[0u8; 4]; side]; side];
+    for (y, row) in pixels_2d.iter_mut().enumerate() {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            *pixel = resized
                 .get_pixel(x.try_into().unwrap(), y.try_into().unwrap())
                 .0;
         }
@@ -65,18 +221,23 @@ pub fn load_and_resize_image_bytes(bytes: &[u8]) -> Result<Vec<Vec<[u8; 4]>>, St
 
 /// Parses bytes as a Crumbicon payload, or if invalid, tries as a regular image.
 ///
-/// # Returns
-/// - `Vec<Option<u8>>`: flattened pixel data (None = transparent, Some = ANSI8 color)
-/// - `Vec<Option<u8>>`: palette of unique ANSI8 colors used
-/// - `u8`: size of the icon (16 for fallback images)
-/// - `String`: path/name of the original Crumbicon file, or modified path (`.svg`) for images.
-pub fn import_bytes(
-    file_name: &str,
-    bytes: &[u8],
-) -> Result<(Vec<Option<u8>>, Vec<Option<u8>>, u8, String), String> {
+/// Crumbicon files carry the active size in `crumbicon-data`; files saved by
+/// Rusticon may also carry the other size variants in `rusticon-data-<size>`.
+/// Regular images are resampled into the 8x8 and 16x16 variants, with 16x16
+/// active and the path rewritten to `.svg`. Shared sessions are passed on
+/// whole in `session`.
+pub fn import_bytes(file_name: &str, bytes: &[u8]) -> Result<ImportPayload, String> {
     // Attempt to read as Crumbicon
     let text = String::from_utf8_lossy(bytes).to_string();
 
+    // A shared session, checked here so a broken one fails like any file.
+    if session_path(&text).is_some() {
+        session_from_json(&text, &mut State::default().editor)?;
+        let mut payload = ImportPayload::blank(file_name.to_string());
+        payload.session = Some(text);
+        return Ok(payload);
+    }
+
     // Extract crumbicon data & palette
     let crumbicon_data = get_crumbicon_data(&text, "<!-- crumbicon-data:", "crumbicon-data -->");
     let crumbicon_palette =
@@ -89,56 +250,62 @@ pub fn import_bytes(
     if !crumbicon_data.is_empty()
         && !crumbicon_palette.is_empty()
         && size as usize * size as usize == crumbicon_data.len()
+        && CANVAS_SIZES.contains(&size)
     {
-        // Valid crumbicon → return original data and path
-        return Ok((
-            crumbicon_data,
-            crumbicon_palette,
-            size,
-            file_name.to_string(),
-        ));
+        // Valid crumbicon → return original data and path, the active size
+        // always wins over a stale variant of the same size.
+        let mut payload = ImportPayload::blank(file_name.to_string());
+        let mut first = Frame::new(vec![Layer::new("Layer 1".to_string())]);
+        let canvases = &mut first.layers[0].canvases;
+        for variant in CANVAS_SIZES {
+            if let Some(data) = get_variant_data(&text, variant as usize) {
+                canvases.insert(variant, data);
+            }
+        }
+        canvases.insert(size, crumbicon_data);
+
+        // Layered documents replace the flattened copy.
+        let layers = get_layers(&text);
+        if !layers.is_empty() {
+            first.layers = layers;
+        }
+        payload.frames = get_frames(&text, first);
+        payload.palette = crumbicon_palette;
+        payload.size = size;
+        payload.export = get_export(&text);
+        return Ok(payload);
     }
 
     // Try loading as regular image
-    match load_and_resize_image_bytes(bytes) {
-        Ok(pixels_2d) => {
-            let mut data = Vec::with_capacity(16 * 16); // flattened pixel array
-            let mut palette = Vec::new(); // unique ANSI8 colors
-
-            // Convert RGBA 16x16 image to Crumbicon-style pixel data
-            for row in &pixels_2d {
-                for &px in row {
-                    let a = px[3]; // alpha channel
-                    let val = if a == 0 {
-                        None // transparent
-                    } else {
-                        let ansi8 = Colors::rgb_to_ansi8([px[0], px[1], px[2]]); // RGB → ANSI8
-                        Some(ansi8)
-                    };
-                    data.push(val);
-
-                    // Track unique palette colors
-                    if let Some(v) = val {
-                        if !palette.contains(&v) {
-                            palette.push(v);
-                        }
-                    }
-                }
-            }
+    match (
+        load_and_resize_image_bytes(bytes, 16),
+        load_and_resize_image_bytes(bytes, 8),
+    ) {
+        (Ok(pixels_16), Ok(pixels_8)) => {
+            // Convert RGBA images to Crumbicon-style pixel data
+            let canvas16_data = pixels_to_data(&pixels_16);
+            let canvas8_data = pixels_to_data(&pixels_8);
+
+            // Most used colors first, so the palette bar keeps the important ones
+            let palette = color_usage(&[&canvas16_data, &canvas8_data])
+                .into_iter()
+                .map(|(code, _)| Some(code))
+                .collect();
 
             // For fallback images, overwrite path with `.svg`
             let mut new_path = Path::new(file_name).to_path_buf();
             new_path.set_extension("svg");
 
-            Ok((
-                data,
-                palette.into_iter().map(Some).collect(),
-                16, // fallback size always 16
-                new_path.to_string_lossy().into_owned(),
-            ))
+            let mut payload = ImportPayload::blank(new_path.to_string_lossy().into_owned());
+            let canvases = &mut payload.frames[0].layers[0].canvases;
+            canvases.insert(16, canvas16_data);
+            canvases.insert(8, canvas8_data);
+            payload.palette = palette;
+            payload.size = 16; // fallback size always 16
+            Ok(payload)
         }
         // Neither valid crumbicon nor image → error
-        Err(_) => Err(format!("Invalid file: {}", file_name)),
+        _ => Err(format!("Invalid file: {}", file_name)),
     }
 }
 
@@ -146,9 +313,7 @@ pub fn import_bytes(
 ///
 /// Native-only path-based importer used by terminal mode.
 #[cfg(not(target_arch = "wasm32"))]
-pub fn import_file(
-    file_path: &str,
-) -> Result<(Vec<Option<u8>>, Vec<Option<u8>>, u8, String), String> {
+pub fn import_file(file_path: &str) -> Result<ImportPayload, String> {
     let path = Path::new(file_path);
 
     if !path.exists() {
@@ -159,10 +324,7 @@ pub fn import_file(
             return import_file(&new_path.to_string_lossy());
         }
 
-        return Ok((
-            vec![None; 8 * 8],
-            vec![None; 8],
-            8,
+        return Ok(ImportPayload::blank(
             new_path.to_string_lossy().into_owned(),
         ));
     }
@@ -170,3 +332,102 @@ pub fn import_file(
     let bytes = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
     import_bytes(file_path, &bytes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::model::FRAME_DELAYS, features::export::build_svg};
+
+    /// A layer with a diagonal of `color` from `offset` in its 8x8 and 16x16
+    /// grids.
+    fn painted_layer(name: &str, color: u8, offset: usize) -> Layer {
+        let mut layer = Layer::new(name.to_string());
+        for (&size, grid) in layer.canvases.iter_mut().filter(|(size, _)| **size <= 16) {
+            for i in (offset..grid.len()).step_by(size as usize + 1) {
+                grid[i] = Some(color);
+            }
+        }
+        layer
+    }
+
+    /// `frames` saved at 8x8, the way the editor saves a document.
+    fn saved(frames: &[Frame], options: &ExportOptions) -> String {
+        build_svg(
+            &frames[0].composite(8),
+            &[Some(196), None],
+            &frames[0].variants(),
+            frames,
+            8,
+            8,
+            options,
+        )
+    }
+
+    #[test]
+    fn layers_round_trip() {
+        let mut hidden = painted_layer("Shade \"&\" <b>", 21, 1);
+        hidden.visible = false;
+        let frames = vec![Frame::new(vec![painted_layer("Base", 196, 0), hidden])];
+        let svg = saved(&frames, &ExportOptions::new());
+
+        assert_eq!(get_layers(&svg), frames[0].layers);
+        let payload = import_bytes("icon.svg", svg.as_bytes()).unwrap();
+        assert_eq!(payload.frames, frames);
+        assert_eq!(payload.size, 8);
+        assert_eq!(payload.palette, vec![Some(196), None]);
+    }
+
+    #[test]
+    fn single_layer_is_saved_flattened() {
+        let frames = vec![Frame::new(vec![painted_layer("Base", 196, 0)])];
+        let svg = saved(&frames, &ExportOptions::new());
+
+        assert!(get_layers(&svg).is_empty());
+        let payload = import_bytes("icon.svg", svg.as_bytes()).unwrap();
+        assert_eq!(
+            payload.frames[0].layers[0].canvases,
+            frames[0].layers[0].canvases
+        );
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        let first = Frame::new(vec![
+            painted_layer("Base", 196, 0),
+            painted_layer("Top", 21, 2),
+        ]);
+        let mut second = Frame::new(vec![
+            painted_layer("Base", 46, 3),
+            Layer::new("Top".to_string()),
+        ]);
+        second.delay = FRAME_DELAYS[4];
+        let frames = vec![first, second];
+        let svg = saved(&frames, &ExportOptions::new());
+
+        assert_eq!(get_frames(&svg, frames[0].clone()), frames);
+        let payload = import_bytes("icon.svg", svg.as_bytes()).unwrap();
+        assert_eq!(payload.frames, frames);
+    }
+
+    #[test]
+    fn export_options_round_trip() {
+        let options = ExportOptions {
+            profile: ExportProfile::AppIcon,
+            px: 16,
+            view_box: true,
+            background: Some(21),
+            radius: 20,
+            padding: 2,
+        };
+        let frames = vec![Frame::new(vec![painted_layer("Base", 196, 0)])];
+        let svg = saved(&frames, &options);
+
+        assert_eq!(get_export(&svg), Some(options.clone()));
+        let payload = import_bytes("icon.svg", svg.as_bytes()).unwrap();
+        assert_eq!(payload.export, Some(options));
+        // Values out of range leave the defaults.
+        let odd = svg.replace(":20:2rusticon-export", ":33:2rusticon-export");
+        assert_ne!(odd, svg);
+        assert_eq!(get_export(&odd), None);
+    }
+}
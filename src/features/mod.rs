@@ -1,3 +1,11 @@
+pub mod animation;
 pub mod export;
+pub mod fill;
+pub mod gallery;
 pub mod import;
 pub mod message;
+pub mod palette_file;
+pub mod session;
+pub mod snap;
+pub mod templates;
+pub mod text_stamp;
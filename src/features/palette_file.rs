@@ -0,0 +1,199 @@
+use incredible::*;
+use std::path::Path;
+
+use crate::core::{color::ansi8_to_rgb, model::PaletteFormat};
+
+/// Format of a palette file, judged by its extension.
+pub fn palette_format(file_name: &str) -> Option<PaletteFormat> {
+    let ext = Path::new(file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())?;
+    PaletteFormat::ALL
+        .into_iter()
+        .find(|format| format.extension() == ext)
+}
+
+/// Parses `RRGGBB`, `#RRGGBB` or Paint.NET `AARRGGBB`.
+fn parse_hex(text: &str) -> Option<[u8; 3]> {
+    let hex = text.trim().trim_start_matches('#');
+    let hex = match hex.len() {
+        6 => hex,
+        8 => hex.get(2..)?,
+        _ => return None,
+    };
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+fn parse_gpl(text: &str) -> Result<Vec<[u8; 3]>, String> {
+    let mut lines = text.lines();
+    if lines.next().map(str::trim) != Some("GIMP Palette") {
+        return Err("Not a GIMP palette.".to_string());
+    }
+
+    Ok(lines
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter(|line| !line.starts_with("Name:") && !line.starts_with("Columns:"))
+        .filter_map(|line| {
+            let mut channels = line.split_whitespace().map(|v| v.parse::<u8>().ok());
+            Some([channels.next()??, channels.next()??, channels.next()??])
+        })
+        .collect())
+}
+
+fn parse_lines(text: &str) -> Vec<[u8; 3]> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with(';'))
+        .filter_map(parse_hex)
+        .collect()
+}
+
+fn parse_json(text: &str) -> Result<Vec<[u8; 3]>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(text).map_err(|_| "Not a JSON palette.".to_string())?;
+    let colors = value
+        .get("colors")
+        .and_then(|c| c.as_array())
+        .ok_or("JSON palette has no colors list.".to_string())?;
+
+    Ok(colors
+        .iter()
+        .filter_map(|c| c.as_str())
+        .filter_map(parse_hex)
+        .collect())
+}
+
+/// Reads a palette file into ANSI colors, snapped to the nearest code and
+/// without duplicates.
+pub fn parse_palette(file_name: &str, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let format =
+        palette_format(file_name).ok_or(format!("{} is not a palette file.", file_name))?;
+    let text = String::from_utf8_lossy(bytes);
+
+    let rgb = match format {
+        PaletteFormat::Gpl => parse_gpl(&text)?,
+        PaletteFormat::PaintNet | PaletteFormat::Hex => parse_lines(&text),
+        PaletteFormat::Json => parse_json(&text)?,
+    };
+    if rgb.is_empty() {
+        return Err(format!("No colors found in {}.", file_name));
+    }
+
+    let mut palette = vec![];
+    for color in rgb {
+        let code = Colors::rgb_to_ansi8(color);
+        if !palette.contains(&code) {
+            palette.push(code);
+        }
+    }
+    Ok(palette)
+}
+
+/// Writes the filled palette slots as a palette file named `name`.
+pub fn build_palette_file(format: PaletteFormat, name: &str, palette: &[Option<u8>]) -> String {
+    let colors: Vec<[u8; 3]> = palette.iter().flatten().map(|&c| ansi8_to_rgb(c)).collect();
+    let hex = |[r, g, b]: [u8; 3]| format!("{:02x}{:02x}{:02x}", r, g, b);
+
+    match format {
+        PaletteFormat::Gpl => {
+            let mut out = format!("GIMP Palette\nName: {}\nColumns: 8\n#\n", name);
+            for [r, g, b] in colors {
+                out.push_str(&format!("{:3} {:3} {:3}\t#{}\n", r, g, b, hex([r, g, b])));
+            }
+            out
+        }
+        PaletteFormat::PaintNet => {
+            let mut out = format!(
+                "; paint.net Palette File\n; Palette Name: {}\n; Colors: {}\n",
+                name,
+                colors.len()
+            );
+            for color in colors {
+                out.push_str(&format!("FF{}\n", hex(color).to_uppercase()));
+            }
+            out
+        }
+        PaletteFormat::Hex => colors.into_iter().map(|c| hex(c) + "\n").collect(),
+        PaletteFormat::Json => {
+            let json = serde_json::json!({
+                "name": name,
+                "colors": colors.into_iter().map(hex).collect::<Vec<_>>(),
+            });
+            serde_json::to_string_pretty(&json).unwrap_or_default()
+        }
+    }
+}
+
+/// Name of the palette file saved next to `icon_path`, e.g.
+/// `favicon-palette.gpl` for `favicon.svg`.
+pub fn palette_file_name(icon_path: &str, format: PaletteFormat) -> String {
+    let path = Path::new(icon_path);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("palette");
+    let name = format!("{}-palette.{}", stem, format.extension());
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_accepts_rgb_and_argb() {
+        assert_eq!(parse_hex("#ff8000"), Some([255, 128, 0]));
+        assert_eq!(parse_hex(" FF102030 "), Some([16, 32, 48]));
+        assert_eq!(parse_hex("12345"), None);
+        assert_eq!(parse_hex("aé12345"), None);
+        assert_eq!(parse_hex("zz0000"), None);
+    }
+
+    #[test]
+    fn gpl_skips_header_and_comments() {
+        let text = "GIMP Palette\nName: Test\nColumns: 4\n#\n255   0   0\tRed\n  0 128 255\n";
+        assert_eq!(parse_gpl(text), Ok(vec![[255, 0, 0], [0, 128, 255]]));
+        assert!(parse_gpl("255 0 0\n").is_err());
+    }
+
+    #[test]
+    fn paint_net_skips_comments() {
+        let text = "; paint.net Palette File\n; Colors: 2\nFFFF0000\nFF00FF00\n";
+        assert_eq!(parse_lines(text), vec![[255, 0, 0], [0, 255, 0]]);
+    }
+
+    #[test]
+    fn hex_lines_skip_blanks() {
+        assert_eq!(
+            parse_lines("ff0000\n\n0000ff\n"),
+            vec![[255, 0, 0], [0, 0, 255]]
+        );
+    }
+
+    #[test]
+    fn json_reads_colors_list() {
+        let text = r##"{"name": "Test", "colors": ["ff0000", "#00ff00", 7]}"##;
+        assert_eq!(parse_json(text), Ok(vec![[255, 0, 0], [0, 255, 0]]));
+        assert!(parse_json(r#"{"name": "Test"}"#).is_err());
+        assert!(parse_json("not json").is_err());
+    }
+
+    #[test]
+    fn palette_files_round_trip() {
+        // Cube colors, which no other code shares.
+        let palette = [Some(67), None, Some(139), Some(208)];
+        for format in PaletteFormat::ALL {
+            let file_name = format!("test.{}", format.extension());
+            let text = build_palette_file(format, "Test", &palette);
+            assert_eq!(
+                parse_palette(&file_name, text.as_bytes()),
+                Ok(vec![67, 139, 208]),
+                "{}",
+                file_name
+            );
+        }
+    }
+}
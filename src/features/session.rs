@@ -0,0 +1,366 @@
+use crate::core::config::EXPORT_PIXEL_SIZES;
+use crate::core::model::{
+    CANVAS_SIZES, EXPORT_PADDINGS, EXPORT_RADII, EditorState, ExportProfile, FILL_TOLERANCES,
+    FRAME_DELAYS, Frame, Layer, MAX_FRAMES, MAX_PALETTE_SLOTS, MAX_ZOOM, PaletteFormat, Panel,
+    StampFont, Vision,
+};
+use serde_json::{Value, json};
+use std::path::Path;
+
+/// Marks a JSON document as a Rusticon session.
+const SESSION_FORMAT: &str = "rusticon-session";
+/// Version written by this build. Older versions still load: fields a
+/// session lacks keep the editor's current value.
+pub const SESSION_VERSION: u64 = 1;
+/// Extension of shared session files, opened like icons.
+const SESSION_EXTENSION: &str = "rusticon";
+
+/// The variant in `all` whose label is `value`.
+fn enum_from<T: Copy>(all: &[T], label: fn(T) -> &'static str, value: &Value) -> Option<T> {
+    let text = value.as_str()?;
+    all.iter().copied().find(|&variant| label(variant) == text)
+}
+
+fn cells_json(cells: &[Option<u8>]) -> Value {
+    Value::Array(cells.iter().map(|cell| json!(cell)).collect())
+}
+
+/// A color code or `null` (void); anything else is invalid.
+fn cell_from(value: &Value) -> Option<Option<u8>> {
+    match value {
+        Value::Null => Some(None),
+        _ => value.as_u64().and_then(|v| u8::try_from(v).ok()).map(Some),
+    }
+}
+
+fn layer_json(layer: &Layer) -> Value {
+    let canvases: serde_json::Map<String, Value> = layer
+        .canvases
+        .iter()
+        .map(|(size, cells)| (size.to_string(), cells_json(cells)))
+        .collect();
+    json!({
+        "name": layer.name,
+        "visible": layer.visible,
+        "canvases": canvases,
+    })
+}
+
+/// A layer; grids of unknown sizes or the wrong length are dropped, sizes
+/// without a grid start blank.
+fn layer_from(value: &Value) -> Option<Layer> {
+    let mut layer = Layer::new(value.get("name")?.as_str()?.to_string());
+    layer.visible = value
+        .get("visible")
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+    for (size, cells) in value.get("canvases")?.as_object()? {
+        let Some(size) = size.parse::<u8>().ok().filter(|s| CANVAS_SIZES.contains(s)) else {
+            continue;
+        };
+        let cells: Option<Vec<Option<u8>>> = cells.as_array()?.iter().map(cell_from).collect();
+        if let Some(cells) = cells.filter(|c| c.len() == size as usize * size as usize) {
+            layer.canvases.insert(size, cells);
+        }
+    }
+    Some(layer)
+}
+
+/// A frame; delays other than the offered ones make it invalid.
+fn frame_from(value: &Value) -> Option<Frame> {
+    let layers: Vec<Layer> = value
+        .get("layers")?
+        .as_array()?
+        .iter()
+        .map(layer_from)
+        .collect::<Option<_>>()?;
+    let delay = value
+        .get("delay")?
+        .as_u64()
+        .and_then(|d| u16::try_from(d).ok())
+        .filter(|d| FRAME_DELAYS.contains(d))?;
+    (!layers.is_empty()).then_some(Frame { layers, delay })
+}
+
+/// The editor's document and tool settings as a versioned JSON session: every
+/// frame, layer and size, the palette, the active size and selected colors.
+/// Transient state (hover, focus, playback, a stamp being typed) is left out.
+pub fn session_to_json(editor: &EditorState) -> String {
+    let frames: Vec<Value> = editor
+        .document_frames(false)
+        .iter()
+        .map(|frame| {
+            json!({
+                "delay": frame.delay,
+                "layers": frame.layers.iter().map(layer_json).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    let fill = &editor.fill;
+    let view = &editor.view;
+    let export = &editor.export;
+
+    let session = json!({
+        "format": SESSION_FORMAT,
+        "version": SESSION_VERSION,
+        "path": editor.file_path,
+        "size": editor.size,
+        "palette": cells_json(&editor.palette_colors),
+        "palette_index": editor.palette_index,
+        "primary": editor.paintbrush,
+        "secondary": editor.secondary,
+        "frames": frames,
+        "active_frame": editor.active_frame,
+        "active_layer": editor.active_layer,
+        "tools": {
+            "fill": {
+                "connectivity8": fill.connectivity8,
+                "global": fill.global,
+                "tolerance": fill.tolerance,
+                "gradient": fill.gradient,
+            },
+            "view": {
+                "checkerboard": view.checkerboard,
+                "grid": view.grid,
+                "guides": view.guides,
+                "crosshair": view.crosshair,
+            },
+            "zoom": editor.zoom,
+            "panel": editor.panel.label(),
+            "vision": editor.vision.label(),
+            "palette_format": editor.palette_format.extension(),
+            "stamp_font": editor.stamp_font.label(),
+            "linked_palette": editor.linked_palette,
+            "snap_dither": editor.snap_dither,
+            "onion_skin": editor.onion_skin,
+            "export_layers": editor.export_layers,
+        },
+        "export": {
+            "profile": export.profile.label(),
+            "px": export.px,
+            "view_box": export.view_box,
+            "background": export.background,
+            "radius": export.radius,
+            "padding": export.padding,
+        },
+    });
+    serde_json::to_string(&session).unwrap_or_default()
+}
+
+/// Name of the session file shared next to `icon_path`, e.g.
+/// `favicon.rusticon` for `favicon.svg`.
+pub fn session_file_name(icon_path: &str) -> String {
+    Path::new(icon_path)
+        .with_extension(SESSION_EXTENSION)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Icon path stored in a session, if `text` is one.
+pub fn session_path(text: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(text).ok()?;
+    if value.get("format")?.as_str()? != SESSION_FORMAT {
+        return None;
+    }
+    Some(value.get("path")?.as_str()?.to_string())
+}
+
+/// Loads a session written by `session_to_json` into `editor`, replacing its
+/// document. Tool settings the session lacks, or holds invalid values for,
+/// are kept. On error `editor` is left untouched.
+pub fn session_from_json(text: &str, editor: &mut EditorState) -> Result<(), String> {
+    let value: Value = serde_json::from_str(text).map_err(|_| "Not a JSON session.".to_string())?;
+    if value.get("format").and_then(Value::as_str) != Some(SESSION_FORMAT) {
+        return Err("Not a Rusticon session.".to_string());
+    }
+    let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version == 0 || version > SESSION_VERSION {
+        return Err(format!("Unsupported session version {}.", version));
+    }
+
+    let size = value
+        .get("size")
+        .and_then(Value::as_u64)
+        .and_then(|s| u8::try_from(s).ok())
+        .filter(|s| CANVAS_SIZES.contains(s))
+        .ok_or("Session has no valid size.".to_string())?;
+    let palette: Vec<Option<u8>> = value
+        .get("palette")
+        .and_then(Value::as_array)
+        .and_then(|colors| colors.iter().map(cell_from).collect::<Option<_>>())
+        .filter(|colors: &Vec<_>| !colors.is_empty() && colors.len() <= MAX_PALETTE_SLOTS)
+        .ok_or("Session has no valid palette.".to_string())?;
+    let mut frames: Vec<Frame> = value
+        .get("frames")
+        .and_then(Value::as_array)
+        .and_then(|frames| frames.iter().map(frame_from).collect::<Option<_>>())
+        .filter(|frames: &Vec<_>| !frames.is_empty() && frames.len() <= MAX_FRAMES)
+        .ok_or("Session has no valid frames.".to_string())?;
+    // Every frame shares the layer structure.
+    let layer_count = frames[0].layers.len();
+    if frames.iter().any(|frame| frame.layers.len() != layer_count) {
+        return Err("Session frames have different layers.".to_string());
+    }
+
+    let index = |key: &str, len: usize| {
+        value
+            .get(key)
+            .and_then(Value::as_u64)
+            .map_or(0, |i| (i as usize).min(len - 1))
+    };
+    let active_frame = index("active_frame", frames.len());
+    let color = |key: &str| value.get(key).and_then(cell_from);
+
+    editor.file_path = value
+        .get("path")
+        .and_then(Value::as_str)
+        .unwrap_or(&editor.file_path)
+        .to_string();
+    editor.set_size(size);
+    editor.palette_index = index("palette_index", palette.len());
+    editor.palette_colors = palette;
+    editor.paintbrush = color("primary").unwrap_or(editor.palette_colors[0]);
+    editor.secondary = color("secondary").unwrap_or(None);
+    editor.layers = std::mem::take(&mut frames[active_frame].layers);
+    editor.frames = frames;
+    editor.active_frame = active_frame;
+    editor.active_layer = index("active_layer", layer_count);
+    editor.candidate = None;
+    editor.picker_mode = false;
+    editor.playing = None;
+    editor.stamp = None;
+
+    let tools = &value["tools"];
+    let flag = |value: &Value, current: bool| value.as_bool().unwrap_or(current);
+    let (fill, view) = (&tools["fill"], &tools["view"]);
+    editor.fill.connectivity8 = flag(&fill["connectivity8"], editor.fill.connectivity8);
+    editor.fill.global = flag(&fill["global"], editor.fill.global);
+    editor.fill.gradient = flag(&fill["gradient"], editor.fill.gradient);
+    if let Some(tolerance) = fill["tolerance"]
+        .as_u64()
+        .and_then(|t| u8::try_from(t).ok())
+        .filter(|t| FILL_TOLERANCES.contains(t))
+    {
+        editor.fill.tolerance = tolerance;
+    }
+    editor.view.checkerboard = flag(&view["checkerboard"], editor.view.checkerboard);
+    editor.view.grid = flag(&view["grid"], editor.view.grid);
+    editor.view.guides = flag(&view["guides"], editor.view.guides);
+    editor.view.crosshair = flag(&view["crosshair"], editor.view.crosshair);
+    // `null` zooms to fit, a missing zoom keeps the current one.
+    match tools.get("zoom") {
+        Some(Value::Null) => editor.zoom = None,
+        Some(zoom) => {
+            if let Some(zoom) = zoom.as_u64() {
+                editor.zoom = Some(zoom.clamp(1, MAX_ZOOM as u64) as u8);
+            }
+        }
+        None => {}
+    }
+    editor.panel = enum_from(&Panel::ALL, Panel::label, &tools["panel"]).unwrap_or(editor.panel);
+    editor.vision =
+        enum_from(&Vision::ALL, Vision::label, &tools["vision"]).unwrap_or(editor.vision);
+    editor.palette_format = enum_from(
+        &PaletteFormat::ALL,
+        PaletteFormat::extension,
+        &tools["palette_format"],
+    )
+    .unwrap_or(editor.palette_format);
+    editor.stamp_font = enum_from(&StampFont::ALL, StampFont::label, &tools["stamp_font"])
+        .unwrap_or(editor.stamp_font);
+    editor.linked_palette = flag(&tools["linked_palette"], editor.linked_palette);
+    editor.snap_dither = flag(&tools["snap_dither"], editor.snap_dither);
+    editor.onion_skin = flag(&tools["onion_skin"], editor.onion_skin);
+    editor.export_layers = flag(&tools["export_layers"], editor.export_layers);
+
+    let export = &value["export"];
+    let small = |value: &Value, allowed: &[u8]| {
+        value
+            .as_u64()
+            .and_then(|v| u8::try_from(v).ok())
+            .filter(|v| allowed.contains(v))
+    };
+    editor.export.profile = enum_from(
+        &ExportProfile::ALL,
+        ExportProfile::label,
+        &export["profile"],
+    )
+    .unwrap_or(editor.export.profile);
+    if let Some(px) = export["px"]
+        .as_u64()
+        .map(|px| px as usize)
+        .filter(|px| EXPORT_PIXEL_SIZES.contains(px))
+    {
+        editor.export.px = px;
+    }
+    editor.export.view_box = flag(&export["view_box"], editor.export.view_box);
+    if let Some(background) = export.get("background").and_then(cell_from) {
+        editor.export.background = background;
+    }
+    editor.export.radius = small(&export["radius"], &EXPORT_RADII).unwrap_or(editor.export.radius);
+    editor.export.padding =
+        small(&export["padding"], &EXPORT_PADDINGS).unwrap_or(editor.export.padding);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::model::State;
+
+    /// A default editor with a painted cell and a few tools changed.
+    fn edited() -> EditorState {
+        let mut editor = State::default().editor;
+        editor.canvas_data_mut()[0] = Some(196);
+        editor.paintbrush = Some(21);
+        editor.fill.global = true;
+        editor.view.grid = true;
+        editor.zoom = Some(3);
+        editor.export.padding = EXPORT_PADDINGS[1];
+        editor
+    }
+
+    #[test]
+    fn session_round_trips() {
+        let session = session_to_json(&edited());
+        let mut loaded = State::default().editor;
+        session_from_json(&session, &mut loaded).unwrap();
+
+        assert_eq!(session_to_json(&loaded), session);
+        assert_eq!(loaded.canvas_data()[0], Some(196));
+        assert_eq!(loaded.paintbrush, Some(21));
+        assert!(loaded.fill.global && loaded.view.grid);
+        assert_eq!(loaded.zoom, Some(3));
+    }
+
+    #[test]
+    fn zoom_is_clamped_and_odd_delays_rejected() {
+        let mut value: Value = serde_json::from_str(&session_to_json(&edited())).unwrap();
+        value["tools"]["zoom"] = json!(99);
+        let mut loaded = State::default().editor;
+        session_from_json(&value.to_string(), &mut loaded).unwrap();
+        assert_eq!(loaded.zoom, Some(MAX_ZOOM));
+
+        value["frames"][0]["delay"] = json!(7);
+        let mut editor = State::default().editor;
+        assert!(session_from_json(&value.to_string(), &mut editor).is_err());
+    }
+
+    #[test]
+    fn newer_session_version_is_rejected() {
+        let mut value: Value = serde_json::from_str(&session_to_json(&edited())).unwrap();
+        value["version"] = json!(SESSION_VERSION + 1);
+        let mut editor = State::default().editor;
+        let before = editor.clone();
+
+        let loaded = session_from_json(&value.to_string(), &mut editor);
+        assert_eq!(
+            loaded,
+            Err(format!(
+                "Unsupported session version {}.",
+                SESSION_VERSION + 1
+            ))
+        );
+        assert_eq!(editor, before);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::core::color::{ansi8_to_rgb, rgb_to_oklab};
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+}
+
+/// Index of the color in `targets` closest to `lab`.
+fn nearest(lab: [f32; 3], targets: &[[f32; 3]]) -> usize {
+    (0..targets.len())
+        .min_by(|&a, &b| distance(lab, targets[a]).total_cmp(&distance(lab, targets[b])))
+        .unwrap_or(0)
+}
+
+/// Re-maps every colored cell of a `size` x `size` grid to the nearest
+/// `palette` color in OKLab. With `dither` the rounding error is spread to
+/// the neighbouring cells (Floyd-Steinberg), so blends survive as patterns.
+/// Void cells stay void.
+pub fn snap_to_palette(data: &mut [Option<u8>], size: usize, palette: &[u8], dither: bool) {
+    if palette.is_empty() {
+        return;
+    }
+    let targets: Vec<[f32; 3]> = palette
+        .iter()
+        .map(|&code| rgb_to_oklab(ansi8_to_rgb(code)))
+        .collect();
+
+    let mut labs: Vec<Option<[f32; 3]>> = data
+        .iter()
+        .map(|cell| cell.map(|code| rgb_to_oklab(ansi8_to_rgb(code))))
+        .collect();
+
+    for row in 0..size {
+        for col in 0..size {
+            let idx = row * size + col;
+            let Some(lab) = labs[idx] else {
+                continue;
+            };
+            let pick = nearest(lab, &targets);
+            data[idx] = Some(palette[pick]);
+
+            if !dither {
+                continue;
+            }
+            let error = [0, 1, 2].map(|i| lab[i] - targets[pick][i]);
+            let spread = [(0, 1, 7.0), (1, -1, 3.0), (1, 0, 5.0), (1, 1, 1.0)];
+            for (dr, dc, weight) in spread {
+                let (r, c) = (row + dr, col as isize + dc);
+                if r >= size || c < 0 || c as usize >= size {
+                    continue;
+                }
+                if let Some(next) = labs[r * size + c as usize].as_mut() {
+                    for (channel, error) in next.iter_mut().zip(error) {
+                        *channel += error * weight / 16.0;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: u8 = 16;
+    const WHITE: u8 = 231;
+    /// #808080, nearer white than black in OKLab.
+    const MID_GRAY: u8 = 244;
+
+    #[test]
+    fn cells_snap_to_the_nearest_color() {
+        let mut data = vec![Some(232), None, Some(255), Some(WHITE)];
+        snap_to_palette(&mut data, 2, &[BLACK, WHITE], false);
+        assert_eq!(data, vec![Some(BLACK), None, Some(WHITE), Some(WHITE)]);
+    }
+
+    #[test]
+    fn empty_palette_changes_nothing() {
+        let mut data = vec![Some(232), None, Some(MID_GRAY), Some(196)];
+        let before = data.clone();
+        snap_to_palette(&mut data, 2, &[], true);
+        assert_eq!(data, before);
+    }
+
+    #[test]
+    fn dithering_keeps_blends_as_patterns() {
+        let mut flat = vec![Some(MID_GRAY); 16];
+        snap_to_palette(&mut flat, 4, &[BLACK, WHITE], false);
+        assert!(flat.iter().all(|cell| *cell == Some(WHITE)));
+
+        let mut dithered = vec![Some(MID_GRAY); 16];
+        snap_to_palette(&mut dithered, 4, &[BLACK, WHITE], true);
+        let blacks = dithered.iter().filter(|cell| **cell == Some(BLACK)).count();
+        assert!(blacks > 0 && blacks < 16);
+        assert!(
+            dithered
+                .iter()
+                .all(|cell| matches!(cell, Some(BLACK | WHITE)))
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::core::{
+    model::{Frame, Layer, StampFont},
+    shared::ImportPayload,
+};
+use crate::features::{import::import_bytes, text_stamp::stamp_text};
+use crate::platform;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Icons from the repository's `gallery/` directory, built in.
+pub const GALLERY: &[(&str, &str)] = &[
+    (
+        "1972_BlueMarble",
+        include_str!("../../gallery/1972_BlueMarble.svg"),
+    ),
+    (
+        "albert-einstein",
+        include_str!("../../gallery/albert-einstein.svg"),
+    ),
+    ("canada", include_str!("../../gallery/canada.svg")),
+    ("lake", include_str!("../../gallery/lake.svg")),
+    (
+        "little_tui_colors",
+        include_str!("../../gallery/little_tui_colors.svg"),
+    ),
+    ("luffy", include_str!("../../gallery/luffy.svg")),
+    ("mondrian", include_str!("../../gallery/mondrian.svg")),
+    ("pinky", include_str!("../../gallery/pinky.svg")),
+    ("ronilan", include_str!("../../gallery/ronilan.svg")),
+    ("rust", include_str!("../../gallery/rust.svg")),
+    ("selfie", include_str!("../../gallery/selfie.svg")),
+    (
+        "selfie-crumbicon",
+        include_str!("../../gallery/selfie-crumbicon.svg"),
+    ),
+];
+
+/// Colors of the built-in shapes: body and letters.
+const SHAPE_COLOR: u8 = 33;
+const LETTER_COLOR: u8 = 231;
+
+/// Where a template comes from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TemplateSource {
+    BuiltIn,
+    Gallery,
+    User,
+}
+
+impl TemplateSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            TemplateSource::BuiltIn => "built-in",
+            TemplateSource::Gallery => "gallery",
+            TemplateSource::User => "yours",
+        }
+    }
+}
+
+/// A starting point for a new document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    pub name: String,
+    pub source: TemplateSource,
+    pub payload: ImportPayload,
+}
+
+/// One layer holding `inside` drawn in `color` at 8x8 and 16x16. `inside`
+/// gets the pixel center in -1..1 on both axes (x right, y down).
+fn shape_layer(inside: impl Fn(f32, f32) -> bool, color: u8) -> Layer {
+    let mut layer = Layer::new("Layer 1".to_string());
+    for size in [8, 16] {
+        let side = size as usize;
+        let at = |i: usize| (2 * i + 1) as f32 / side as f32 - 1.0;
+        let grid = (0..side * side)
+            .map(|i| inside(at(i % side), at(i / side)).then_some(color))
+            .collect();
+        layer.canvases.insert(size, grid);
+    }
+    layer
+}
+
+fn rounded_square(x: f32, y: f32) -> bool {
+    let radius = 0.5;
+    let dx = (x.abs() - (1.0 - radius)).max(0.0);
+    let dy = (y.abs() - (1.0 - radius)).max(0.0);
+    dx * dx + dy * dy <= radius * radius
+}
+
+/// Rounded square with `text` centered on it, in the font suiting each size.
+fn letter_tile(text: &str) -> Layer {
+    let mut layer = shape_layer(rounded_square, SHAPE_COLOR);
+    for size in [8, 16] {
+        let Some(grid) = layer.canvases.get_mut(&size) else {
+            continue;
+        };
+        let font = StampFont::for_size(size);
+        let (width, height) = font.glyph_size();
+        let count = text.chars().count();
+        let text_width = count * (width + 1) - 1;
+        let side = size as usize;
+        let origin = ((side - height) / 2, side.saturating_sub(text_width) / 2);
+        stamp_text(grid, side, origin, text, font, Some(LETTER_COLOR));
+    }
+    layer
+}
+
+fn built_in(name: &str, layer: Layer) -> Template {
+    Template {
+        name: name.to_string(),
+        source: TemplateSource::BuiltIn,
+        payload: ImportPayload {
+            frames: vec![Frame::new(vec![layer])],
+            palette: vec![Some(SHAPE_COLOR), Some(LETTER_COLOR)],
+            size: 16,
+            path: String::new(),
+            export: None,
+            session: None,
+        },
+    }
+}
+
+/// Templates read from files, named after the file.
+fn from_files(files: Vec<(String, Vec<u8>)>, source: TemplateSource) -> Vec<Template> {
+    files
+        .into_iter()
+        .filter_map(|(file_name, bytes)| {
+            let payload = import_bytes(&file_name, &bytes).ok()?;
+            let name = Path::new(&file_name)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or(file_name);
+            Some(Template {
+                name,
+                source,
+                payload,
+            })
+        })
+        .collect()
+}
+
+static TEMPLATES: LazyLock<Vec<Template>> = LazyLock::new(|| {
+    let mut templates = vec![
+        Template {
+            name: "Blank".to_string(),
+            source: TemplateSource::BuiltIn,
+            payload: ImportPayload::blank(String::new()),
+        },
+        built_in(
+            "Circle",
+            shape_layer(|x, y| x * x + y * y <= 1.0, SHAPE_COLOR),
+        ),
+        built_in("Rounded", shape_layer(rounded_square, SHAPE_COLOR)),
+        built_in("Tile A", letter_tile("A")),
+        built_in("Tile AB", letter_tile("AB")),
+        built_in(
+            "Ring",
+            shape_layer(|x, y| (0.3..=1.0).contains(&(x * x + y * y)), SHAPE_COLOR),
+        ),
+        built_in(
+            "Shield",
+            shape_layer(
+                |x, y| y >= -0.9 && x.abs() <= 0.9 * (1.0 - y).min(1.0),
+                SHAPE_COLOR,
+            ),
+        ),
+        built_in(
+            "Diamond",
+            shape_layer(|x, y| x.abs() + y.abs() <= 1.0, SHAPE_COLOR),
+        ),
+    ];
+
+    let gallery = GALLERY
+        .iter()
+        .map(|(name, svg)| (format!("{}.svg", name), svg.as_bytes().to_vec()))
+        .collect();
+    templates.extend(from_files(gallery, TemplateSource::Gallery));
+
+    let user = platform::get_io().user_templates();
+    templates.extend(from_files(user, TemplateSource::User));
+    templates
+});
+
+/// Every template: the built-in shapes (blank first), the gallery icons and
+/// the files in the user templates folder.
+pub fn templates() -> &'static [Template] {
+    &TEMPLATES
+}
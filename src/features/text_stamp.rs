@@ -0,0 +1,271 @@
+use crate::core::model::StampFont;
+
+// The bundled `incredible_elements_text_fonts` are Figlet fonts, drawing each
+// character as lines of ASCII art rather than a grid of pixels, so stamps use
+// pixel fonts of their own.
+
+/// Most characters a stamp holds.
+pub const MAX_STAMP_LEN: usize = 8;
+
+/// 3x5 glyphs, `#` marking a painted pixel.
+const SMALL: &[(char, [&str; 5])] = &[
+    (' ', ["...", "...", "...", "...", "..."]),
+    ('A', [".#.", "#.#", "###", "#.#", "#.#"]),
+    ('B', ["##.", "#.#", "##.", "#.#", "##."]),
+    ('C', [".##", "#..", "#..", "#..", ".##"]),
+    ('D', ["##.", "#.#", "#.#", "#.#", "##."]),
+    ('E', ["###", "#..", "##.", "#..", "###"]),
+    ('F', ["###", "#..", "##.", "#..", "#.."]),
+    ('G', [".##", "#..", "#.#", "#.#", ".##"]),
+    ('H', ["#.#", "#.#", "###", "#.#", "#.#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..#", "..#", "..#", "#.#", ".#."]),
+    ('K', ["#.#", "#.#", "##.", "#.#", "#.#"]),
+    ('L', ["#..", "#..", "#..", "#..", "###"]),
+    ('M', ["#.#", "###", "###", "#.#", "#.#"]),
+    ('N', ["##.", "#.#", "#.#", "#.#", "#.#"]),
+    ('O', [".#.", "#.#", "#.#", "#.#", ".#."]),
+    ('P', ["##.", "#.#", "##.", "#..", "#.."]),
+    ('Q', [".#.", "#.#", "#.#", "##.", ".##"]),
+    ('R', ["##.", "#.#", "##.", "#.#", "#.#"]),
+    ('S', [".##", "#..", ".#.", "..#", "##."]),
+    ('T', ["###", ".#.", ".#.", ".#.", ".#."]),
+    ('U', ["#.#", "#.#", "#.#", "#.#", "###"]),
+    ('V', ["#.#", "#.#", "#.#", "#.#", ".#."]),
+    ('W', ["#.#", "#.#", "###", "###", "#.#"]),
+    ('X', ["#.#", "#.#", ".#.", "#.#", "#.#"]),
+    ('Y', ["#.#", "#.#", ".#.", ".#.", ".#."]),
+    ('Z', ["###", "..#", ".#.", "#..", "###"]),
+    ('a', ["...", ".##", "#.#", "#.#", ".##"]),
+    ('b', ["#..", "##.", "#.#", "#.#", "##."]),
+    ('c', ["...", ".##", "#..", "#..", ".##"]),
+    ('d', ["..#", ".##", "#.#", "#.#", ".##"]),
+    ('e', ["...", ".#.", "###", "#..", ".##"]),
+    ('f', [".##", ".#.", "###", ".#.", ".#."]),
+    ('g', ["...", ".##", "#.#", ".##", "##."]),
+    ('h', ["#..", "#..", "##.", "#.#", "#.#"]),
+    ('i', [".#.", "...", "##.", ".#.", "###"]),
+    ('j', ["..#", "...", "..#", "#.#", ".#."]),
+    ('k', ["#..", "#.#", "##.", "##.", "#.#"]),
+    ('l', ["##.", ".#.", ".#.", ".#.", "###"]),
+    ('m', ["...", "###", "###", "#.#", "#.#"]),
+    ('n', ["...", "##.", "#.#", "#.#", "#.#"]),
+    ('o', ["...", ".#.", "#.#", "#.#", ".#."]),
+    ('p', ["...", "##.", "#.#", "##.", "#.."]),
+    ('q', ["...", ".##", "#.#", ".##", "..#"]),
+    ('r', ["...", "#.#", "##.", "#..", "#.."]),
+    ('s', ["...", ".##", "#..", "..#", "##."]),
+    ('t', [".#.", "###", ".#.", ".#.", "..#"]),
+    ('u', ["...", "#.#", "#.#", "#.#", ".##"]),
+    ('v', ["...", "#.#", "#.#", "#.#", ".#."]),
+    ('w', ["...", "#.#", "#.#", "###", "###"]),
+    ('x', ["...", "#.#", ".#.", ".#.", "#.#"]),
+    ('y', ["...", "#.#", "#.#", ".##", "##."]),
+    ('z', ["...", "###", ".#.", "#..", "###"]),
+    ('0', ["###", "#.#", "#.#", "#.#", "###"]),
+    ('1', [".#.", "##.", ".#.", ".#.", "###"]),
+    ('2', ["##.", "..#", ".#.", "#..", "###"]),
+    ('3', ["##.", "..#", ".#.", "..#", "##."]),
+    ('4', ["#.#", "#.#", "###", "..#", "..#"]),
+    ('5', ["###", "#..", "##.", "..#", "##."]),
+    ('6', [".##", "#..", "###", "#.#", "###"]),
+    ('7', ["###", "..#", ".#.", ".#.", ".#."]),
+    ('8', ["###", "#.#", "###", "#.#", "###"]),
+    ('9', ["###", "#.#", "###", "..#", "##."]),
+    ('!', [".#.", ".#.", ".#.", "...", ".#."]),
+    ('?', ["##.", "..#", ".#.", "...", ".#."]),
+    ('.', ["...", "...", "...", "...", ".#."]),
+    ('-', ["...", "...", "###", "...", "..."]),
+    ('+', ["...", ".#.", "###", ".#.", "..."]),
+    ('&', [".#.", "#.#", ".#.", "#.#", ".##"]),
+    ('#', ["#.#", "###", "#.#", "###", "#.#"]),
+];
+
+/// 4x6 glyphs, `#` marking a painted pixel.
+const LARGE: &[(char, [&str; 6])] = &[
+    (' ', ["....", "....", "....", "....", "....", "...."]),
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".###", "#...", "#...", "#...", "#...", ".###"]),
+    ('D', ["###.", "#..#", "#..#", "#..#", "#..#", "###."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".###", "#...", "#.##", "#..#", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###.", ".#..", ".#..", ".#..", ".#..", "###."]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#..#", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('M', ["#..#", "####", "####", "#..#", "#..#", "#..#"]),
+    ('N', ["#..#", "##.#", "##.#", "#.##", "#.##", "#..#"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('Q', [".##.", "#..#", "#..#", "#..#", "#.#.", ".#.#"]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", ".##.", "...#", "...#", "###."]),
+    ('T', ["###.", ".#..", ".#..", ".#..", ".#..", ".#.."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('V', ["#..#", "#..#", "#..#", "#..#", ".##.", ".##."]),
+    ('W', ["#..#", "#..#", "#..#", "####", "####", "#..#"]),
+    ('X', ["#..#", "#..#", ".##.", ".##.", "#..#", "#..#"]),
+    ('Y', ["#.#.", "#.#.", ".#..", ".#..", ".#..", ".#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ('a', ["....", "....", ".###", "#..#", "#..#", ".###"]),
+    ('b', ["#...", "#...", "###.", "#..#", "#..#", "###."]),
+    ('c', ["....", "....", ".###", "#...", "#...", ".###"]),
+    ('d', ["...#", "...#", ".###", "#..#", "#..#", ".###"]),
+    ('e', ["....", "....", ".##.", "####", "#...", ".###"]),
+    ('f', ["..##", ".#..", "###.", ".#..", ".#..", ".#.."]),
+    ('g', ["....", ".###", "#..#", ".###", "...#", ".##."]),
+    ('h', ["#...", "#...", "###.", "#..#", "#..#", "#..#"]),
+    ('i', [".#..", "....", "##..", ".#..", ".#..", "###."]),
+    ('j', ["..#.", "....", "..#.", "..#.", "#.#.", ".#.."]),
+    ('k', ["#...", "#...", "#..#", "###.", "#..#", "#..#"]),
+    ('l', ["##..", ".#..", ".#..", ".#..", ".#..", "###."]),
+    ('m', ["....", "....", "#.#.", "####", "#..#", "#..#"]),
+    ('n', ["....", "....", "###.", "#..#", "#..#", "#..#"]),
+    ('o', ["....", "....", ".##.", "#..#", "#..#", ".##."]),
+    ('p', ["....", "###.", "#..#", "###.", "#...", "#..."]),
+    ('q', ["....", ".###", "#..#", ".###", "...#", "...#"]),
+    ('r', ["....", "....", "#.##", "##..", "#...", "#..."]),
+    ('s', ["....", "....", ".###", "##..", "..##", "###."]),
+    ('t', [".#..", ".#..", "###.", ".#..", ".#..", "..#."]),
+    ('u', ["....", "....", "#..#", "#..#", "#..#", ".###"]),
+    ('v', ["....", "....", "#..#", "#..#", ".##.", ".##."]),
+    ('w', ["....", "....", "#..#", "####", "####", ".##."]),
+    ('x', ["....", "....", "#..#", ".##.", ".##.", "#..#"]),
+    ('y', ["....", "#..#", "#..#", ".###", "...#", ".##."]),
+    ('z', ["....", "....", "####", "..#.", ".#..", "####"]),
+    ('0', [".##.", "#..#", "#.##", "##.#", "#..#", ".##."]),
+    ('1', [".#..", "##..", ".#..", ".#..", ".#..", "###."]),
+    ('2', [".##.", "#..#", "..#.", ".#..", "#...", "####"]),
+    ('3', ["###.", "...#", ".##.", "...#", "...#", "###."]),
+    ('4', ["#..#", "#..#", "####", "...#", "...#", "...#"]),
+    ('5', ["####", "#...", "###.", "...#", "...#", "###."]),
+    ('6', [".##.", "#...", "###.", "#..#", "#..#", ".##."]),
+    ('7', ["####", "...#", "..#.", ".#..", ".#..", ".#.."]),
+    ('8', [".##.", "#..#", ".##.", "#..#", "#..#", ".##."]),
+    ('9', [".##.", "#..#", "#..#", ".###", "...#", ".##."]),
+    ('!', [".#..", ".#..", ".#..", ".#..", "....", ".#.."]),
+    ('?', [".##.", "#..#", "..#.", ".#..", "....", ".#.."]),
+    ('.', ["....", "....", "....", "....", "....", ".#.."]),
+    ('-', ["....", "....", "####", "....", "....", "...."]),
+    ('+', ["....", ".#..", "###.", ".#..", "....", "...."]),
+    ('&', [".#..", "#.#.", ".#..", "#.##", "#.#.", ".#.#"]),
+    ('#', [".#.#", "####", ".#.#", "####", ".#.#", "...."]),
+];
+
+/// Rows of the glyph for `c`, if the font has one.
+fn glyph(c: char, font: StampFont) -> Option<&'static [&'static str]> {
+    match font {
+        StampFont::Small => SMALL
+            .iter()
+            .find(|(key, _)| *key == c)
+            .map(|(_, rows)| rows.as_slice()),
+        StampFont::Large => LARGE
+            .iter()
+            .find(|(key, _)| *key == c)
+            .map(|(_, rows)| rows.as_slice()),
+    }
+}
+
+/// Whether `c` can be stamped with `font`.
+pub fn has_glyph(c: char, font: StampFont) -> bool {
+    glyph(c, font).is_some()
+}
+
+/// Pixels (row, col) covered by `text` with its top-left corner at (0, 0),
+/// glyphs one pixel apart.
+pub fn text_cells(text: &str, font: StampFont) -> Vec<(usize, usize)> {
+    let (width, _) = font.glyph_size();
+    let mut cells = vec![];
+    for (i, rows) in text.chars().filter_map(|c| glyph(c, font)).enumerate() {
+        let left = i * (width + 1);
+        for (row, line) in rows.iter().enumerate() {
+            for (col, pixel) in line.chars().enumerate() {
+                if pixel == '#' {
+                    cells.push((row, left + col));
+                }
+            }
+        }
+    }
+    cells
+}
+
+/// Paints `text` in `color` onto a `size` x `size` grid with its top-left
+/// corner at `origin` (row, col). Pixels falling off the grid are dropped.
+pub fn stamp_text(
+    data: &mut [Option<u8>],
+    size: usize,
+    origin: (usize, usize),
+    text: &str,
+    font: StampFont,
+    color: Option<u8>,
+) {
+    for (row, col) in text_cells(text, font) {
+        let (row, col) = (origin.0 + row, origin.1 + col);
+        if row < size && col < size {
+            data[row * size + col] = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyphs_fill_their_size() {
+        for font in StampFont::ALL {
+            let (width, height) = font.glyph_size();
+            for c in (' '..='~').filter(|&c| has_glyph(c, font)) {
+                let rows = glyph(c, font).unwrap();
+                assert_eq!(rows.len(), height, "{:?} {:?}", font, c);
+                assert!(
+                    rows.iter().all(|row| row.len() == width),
+                    "{:?} {:?}",
+                    font,
+                    c
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_letter_and_digit_has_a_glyph() {
+        for font in StampFont::ALL {
+            let all = ('a'..='z').chain('A'..='Z').chain('0'..='9');
+            assert!(all.clone().all(|c| has_glyph(c, font)), "{:?}", font);
+            assert_ne!(text_cells("a", font), text_cells("A", font));
+        }
+    }
+
+    #[test]
+    fn glyphs_are_one_pixel_apart() {
+        let i = text_cells("I", StampFont::Small);
+        assert_eq!(i.len(), 9);
+        assert!(i.contains(&(0, 0)) && i.contains(&(4, 2)));
+
+        let two = text_cells("II", StampFont::Small);
+        let shifted: Vec<_> = i.iter().map(|&(row, col)| (row, col + 4)).collect();
+        assert_eq!(two.len(), 18);
+        assert!(shifted.iter().all(|cell| two.contains(cell)));
+        // Characters without a glyph take no room.
+        assert_eq!(text_cells("I~I", StampFont::Small), two);
+    }
+
+    #[test]
+    fn stamp_is_clipped_at_the_canvas_edge() {
+        let size = 8;
+        let mut data = vec![None; size * size];
+        stamp_text(&mut data, size, (5, 6), "I", StampFont::Small, Some(9));
+
+        // Rows 5-7 and columns 6-7 of the 3x5 "I" stay on the grid.
+        let painted: Vec<usize> = (0..data.len()).filter(|&i| data[i].is_some()).collect();
+        assert_eq!(painted, vec![5 * 8 + 6, 5 * 8 + 7, 6 * 8 + 7, 7 * 8 + 7]);
+
+        let mut data = vec![None; size * size];
+        stamp_text(&mut data, size, (8, 0), "I", StampFont::Small, Some(9));
+        assert!(data.iter().all(Option::is_none));
+    }
+}
@@ -6,7 +6,7 @@ pub mod runtime;
 pub mod screens;
 pub mod ui;
 
-pub use core::model::{AppPhase, MIN_SPLASH_MS, State};
+pub use core::model::{AppPhase, State};
 
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen::prelude::wasm_bindgen]
@@ -16,7 +16,7 @@ mod ui;
 #[cfg(feature = "macos-native")]
 pub use crate::core::io::RusticonIo;
 #[cfg(feature = "macos-native")]
-pub use crate::core::model::{AppPhase, MIN_SPLASH_MS, State};
+pub use crate::core::model::{AppPhase, State};
 
 #[cfg(feature = "macos-native")]
 fn main() {
@@ -1,4 +1,4 @@
-use crate::core::io::RusticonIo;
+use crate::core::{config::load_config, io::RusticonIo};
 use std::cell::RefCell;
 use std::sync::Arc;
 
@@ -28,7 +28,11 @@ pub fn init() {
     #[cfg(target_arch = "wasm32")]
     console_error_panic_hook::set_once();
 
-    set_io(Arc::new(io()));
+    let io = io();
+    if let Some(text) = io.load_config() {
+        load_config(&text);
+    }
+    set_io(Arc::new(io));
 }
 
 pub fn io() -> impl crate::core::io::RusticonIo + Clone + 'static {
@@ -1,17 +1,76 @@
-use std::{env, thread};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+};
 
 use crate::{
     core::{
+        config::config,
         io::RusticonIo,
-        model::{AppPhase, State},
+        model::{AppPhase, MAX_RECENT, State},
         shared::{ImportOutcome, RESULT_HOLDER},
     },
-    features::{export::export_svg, import::import_file, message::draw_message},
+    features::{
+        export::{document_svg, export_svg},
+        import::import_file,
+        message::draw_message,
+        session::session_path,
+    },
 };
 use incredible_elements_extra::{DroppedItem, DroppedSource};
 
 pub type FileHandle = String;
 
+/// User preferences in the config directory.
+const CONFIG_FILE: &str = "config.toml";
+/// File in the config directory listing recent files, one path per line.
+const RECENT_FILE: &str = "recent";
+/// File in the data directory holding the autosaved session.
+const RECOVERY_FILE: &str = "recovery.json";
+/// Command line flag reopening the most recent file.
+const LAST_FLAG: &str = "--last";
+/// Command line flag opening the launch screen and its templates.
+const NEW_FLAG: &str = "--new";
+
+/// Rusticon's folder in the user config directory: `$XDG_CONFIG_HOME/rusticon`,
+/// `%APPDATA%\rusticon` or `~/.config/rusticon`.
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("rusticon"))
+}
+
+/// Rusticon's folder in the user data directory: `$XDG_DATA_HOME/rusticon`,
+/// `%LOCALAPPDATA%\rusticon` or `~/.local/share/rusticon`.
+pub(crate) fn data_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+        })?;
+    Some(base.join("rusticon"))
+}
+
+/// Recent file paths, most recent first.
+fn recent_paths() -> Vec<String> {
+    config_dir()
+        .and_then(|dir| fs::read_to_string(dir.join(RECENT_FILE)).ok())
+        .map(|text| {
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 #[derive(Clone, Default)]
 pub struct NativeIo;
 
@@ -23,13 +82,23 @@ impl NativeIo {
 
 impl RusticonIo for NativeIo {
     fn initial_file_path(&self) -> String {
-        env::args()
-            .nth(1)
-            .unwrap_or_else(|| "favicon.svg".to_string())
+        let last = || recent_paths().into_iter().next();
+        match env::args().nth(1) {
+            Some(arg) if arg == LAST_FLAG => last(),
+            Some(arg) if arg == NEW_FLAG => None,
+            arg => arg,
+        }
+        .unwrap_or_else(|| config().file_name.clone())
     }
 
     fn initial_phase(&self) -> AppPhase {
-        AppPhase::Splash
+        // The launch screen and its templates are asked for; a directory
+        // opens in the gallery browser.
+        match env::args().nth(1) {
+            Some(arg) if arg == NEW_FLAG => AppPhase::Launch,
+            Some(arg) if Path::new(&arg).is_dir() => AppPhase::Gallery,
+            _ => AppPhase::Splash,
+        }
     }
 
     fn start_import(&self, path: String) {
@@ -64,22 +133,131 @@ impl RusticonIo for NativeIo {
         draw_message(msg, color_code);
     }
 
-    fn perform_save(&self, state: &State) {
-        let (data, size) = if state.editor.size == 16 {
-            (state.editor.canvas16_data.clone(), 16)
-        } else {
-            (state.editor.canvas8_data.clone(), 8)
+    fn perform_save(&self, state: &State) -> Result<(), String> {
+        let svg = document_svg(&state.editor);
+        let result = export_svg(&svg, &state.editor.file_path);
+        if let Err(err_msg) = &result {
+            self.report_message(err_msg, 196);
+        }
+        result
+    }
+
+    fn save_file(&self, path: &str, bytes: &[u8]) -> Result<(), String> {
+        fs::write(path, bytes).map_err(|e| format!("Failed to save {}: {}", path, e))
+    }
+
+    fn read_file(&self, path: &str) -> Result<Vec<u8>, String> {
+        fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))
+    }
+
+    fn gallery_files(&self, dir: &str) -> Vec<(String, Vec<u8>)> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return vec![];
+        };
+        entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+            })
+            .filter_map(|path| {
+                let bytes = fs::read(&path).ok()?;
+                Some((path.to_string_lossy().into_owned(), bytes))
+            })
+            .collect()
+    }
+
+    fn recent_files(&self) -> Vec<(String, Vec<u8>)> {
+        recent_paths()
+            .into_iter()
+            .filter_map(|path| {
+                let bytes = fs::read(&path).ok()?;
+                Some((path, bytes))
+            })
+            .collect()
+    }
+
+    fn remember_recent(&self, path: &str, _svg: &str) {
+        // Only files on disk can be reopened; new ones count once saved.
+        let (Some(dir), Ok(path)) = (config_dir(), fs::canonicalize(path)) else {
+            return;
+        };
+        let path = path.to_string_lossy().into_owned();
+        let mut paths = recent_paths();
+        if paths.first() == Some(&path) {
+            return;
+        }
+        paths.retain(|recent| *recent != path);
+        paths.insert(0, path);
+        paths.truncate(MAX_RECENT);
+
+        let written = fs::create_dir_all(&dir)
+            .and_then(|_| fs::write(dir.join(RECENT_FILE), paths.join("\n") + "\n"));
+        if let Err(e) = written {
+            self.report_message(&format!("Failed to save recent files: {}", e), 196);
+        }
+    }
+
+    fn autosave(&self, session: &str) {
+        let Some(dir) = data_dir() else {
+            return;
+        };
+        let written =
+            fs::create_dir_all(&dir).and_then(|_| fs::write(dir.join(RECOVERY_FILE), session));
+        if let Err(e) = written {
+            self.report_message(&format!("Failed to autosave: {}", e), 196);
+        }
+    }
+
+    fn clear_autosave(&self) {
+        if let Some(dir) = data_dir() {
+            let _ = fs::remove_file(dir.join(RECOVERY_FILE));
+        }
+    }
+
+    fn recovery(&self) -> Option<String> {
+        let file = data_dir()?.join(RECOVERY_FILE);
+        let session = fs::read_to_string(&file).ok()?;
+        let path = session_path(&session)?;
+
+        // A file saved after the last autosave already holds the work.
+        let modified = |path: &Path| fs::metadata(path).and_then(|meta| meta.modified()).ok();
+        let newer = match (modified(&file), modified(Path::new(&path))) {
+            (Some(recovery), Some(target)) => recovery > target,
+            (recovery, _) => recovery.is_some(),
         };
+        newer.then_some(session)
+    }
 
-        if let Err(err_msg) = export_svg(
-            &data,
-            &state.editor.palette_colors,
-            size,
-            size,
-            32,
-            &state.editor.file_path,
-        ) {
-            self.report_message(&err_msg, 196);
+    fn load_config(&self) -> Option<String> {
+        fs::read_to_string(config_dir()?.join(CONFIG_FILE)).ok()
+    }
+
+    fn save_config(&self, text: &str) {
+        let Some(dir) = config_dir() else {
+            return;
+        };
+        let written = fs::create_dir_all(&dir).and_then(|_| fs::write(dir.join(CONFIG_FILE), text));
+        if let Err(e) = written {
+            self.report_message(&format!("Failed to save settings: {}", e), 196);
         }
     }
+
+    fn user_templates(&self) -> Vec<(String, Vec<u8>)> {
+        let Some(Ok(entries)) = config_dir().map(|dir| fs::read_dir(dir.join("templates"))) else {
+            return vec![];
+        };
+        let mut files: Vec<(String, Vec<u8>)> = entries
+            .flatten()
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                Some((name, fs::read(entry.path()).ok()?))
+            })
+            .collect();
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        files
+    }
 }
@@ -3,19 +3,43 @@ use std::sync::{Arc, LazyLock, Mutex};
 use crate::{
     State,
     core::{
+        config::config,
         io::RusticonIo,
-        model::AppPhase,
-        shared::{ImportOutcome, RESULT_HOLDER},
+        model::{AppPhase, MAX_RECENT},
+        shared::{ImportOutcome, ImportPayload, RESULT_HOLDER},
+    },
+    features::{
+        export::document_svg, import::import_bytes, message::draw_message, templates::GALLERY,
     },
-    features::{export::build_svg, import::import_bytes, message::draw_message},
 };
 use incredible_elements_extra::DroppedItem;
 use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::{JsFuture, spawn_local};
-use web_sys::{Blob, FileSystemFileHandle, FileSystemWritableFileStream, HtmlAnchorElement, Url};
+use web_sys::{
+    Blob, FileSystemFileHandle, FileSystemWritableFileStream, HtmlAnchorElement, Storage, Url,
+};
 
 pub type FileHandle = JsValue;
 
+/// localStorage key of the user's `config.toml` text.
+const CONFIG_KEY: &str = "rusticon-config";
+/// localStorage key of the recent files: a JSON array of `{path, svg}`.
+const RECENT_KEY: &str = "rusticon-recent";
+/// localStorage key of the autosaved session (see `features::session`).
+const RECOVERY_KEY: &str = "rusticon-recovery";
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// Recent files kept in localStorage, most recent first.
+fn recent_entries() -> Vec<serde_json::Value> {
+    local_storage()
+        .and_then(|storage| storage.get_item(RECENT_KEY).ok()?)
+        .and_then(|text| serde_json::from_str::<Vec<serde_json::Value>>(&text).ok())
+        .unwrap_or_default()
+}
+
 #[derive(Clone, Default)]
 pub struct WasmIo;
 
@@ -80,12 +104,21 @@ impl WasmIo {
     /// Classic <a download> fallback for browsers without showSaveFilePicker
     /// (Firefox, Safari, …).
     fn download_file(&self, content: &str, filename: &str) -> Result<(), JsValue> {
+        self.download_part(&JsValue::from_str(content), filename)
+    }
+
+    fn download_bytes(&self, bytes: &[u8], filename: &str) -> Result<(), JsValue> {
+        self.download_part(&js_sys::Uint8Array::from(bytes), filename)
+    }
+
+    /// Downloads one blob part, text or bytes, as `filename`.
+    fn download_part(&self, part: &JsValue, filename: &str) -> Result<(), JsValue> {
         let window = web_sys::window().ok_or(JsValue::from_str("no window"))?;
         let document = window.document().ok_or(JsValue::from_str("no document"))?;
 
         // Blob (no options variant — keeps required web-sys features minimal)
         let parts = js_sys::Array::new();
-        parts.push(&JsValue::from_str(content));
+        parts.push(part);
         let blob = Blob::new_with_str_sequence(&parts)?;
 
         // Object URL
@@ -122,7 +155,7 @@ impl WasmIo {
 
 impl RusticonIo for WasmIo {
     fn initial_file_path(&self) -> String {
-        "favicon.svg".to_string()
+        config().file_name.clone()
     }
 
     fn initial_phase(&self) -> AppPhase {
@@ -134,12 +167,7 @@ impl RusticonIo for WasmIo {
     }
 
     fn start_import(&self, path: String) {
-        let outcome: ImportOutcome = Ok((
-            vec![None; 8 * 8],
-            vec![None; 8],
-            8,
-            self.normalize_svg_name(&path),
-        ));
+        let outcome: ImportOutcome = Ok(ImportPayload::blank(self.normalize_svg_name(&path)));
         let mut guard = RESULT_HOLDER.lock().unwrap();
         *guard = Some(outcome);
     }
@@ -168,7 +196,7 @@ impl RusticonIo for WasmIo {
         // `.svg` name (state.editor.file_path). We only keep the handle when
         // the file name was unchanged (already an SVG/Crumbicon).
         let keep_handle = match &outcome {
-            Ok((_, _, _, returned_path)) => returned_path.eq_ignore_ascii_case(&file_name),
+            Ok(payload) => payload.path.eq_ignore_ascii_case(&file_name),
             Err(_) => false,
         };
 
@@ -185,14 +213,10 @@ impl RusticonIo for WasmIo {
         draw_message(msg, color_code);
     }
 
-    fn perform_save(&self, state: &State) {
-        let (data, size) = if state.editor.size == 16 {
-            (state.editor.canvas16_data.clone(), 16)
-        } else {
-            (state.editor.canvas8_data.clone(), 8)
-        };
-
-        let svg = build_svg(&data, &state.editor.palette_colors, size, size, 32);
+    /// The browser saves asynchronously and reports its own failures, so
+    /// starting the save always succeeds.
+    fn perform_save(&self, state: &State) -> Result<(), String> {
+        let svg = document_svg(&state.editor);
 
         let io = self.clone();
         let handle = state.editor.file_handle.clone();
@@ -230,6 +254,84 @@ impl RusticonIo for WasmIo {
                 }
             }
         });
+        Ok(())
+    }
+
+    fn save_file(&self, path: &str, bytes: &[u8]) -> Result<(), String> {
+        self.download_bytes(bytes, path)
+            .map_err(|_| format!("Failed to download {}.", path))
+    }
+
+    fn gallery_files(&self, _dir: &str) -> Vec<(String, Vec<u8>)> {
+        // No directory access in the browser: browse the built-in gallery.
+        GALLERY
+            .iter()
+            .map(|(name, svg)| (format!("{}.svg", name), svg.as_bytes().to_vec()))
+            .collect()
+    }
+
+    fn recent_files(&self) -> Vec<(String, Vec<u8>)> {
+        recent_entries()
+            .iter()
+            .filter_map(|entry| {
+                let path = entry["path"].as_str()?;
+                let svg = entry["svg"].as_str()?;
+                Some((path.to_string(), svg.as_bytes().to_vec()))
+            })
+            .collect()
+    }
+
+    fn remember_recent(&self, path: &str, svg: &str) {
+        // There is no file to reopen in the browser, so the document itself
+        // is stored, and rewritten only when it or its place changes.
+        let mut entries = recent_entries();
+        let unchanged = entries.first().is_some_and(|entry| {
+            entry["path"].as_str() == Some(path) && entry["svg"].as_str() == Some(svg)
+        });
+        if unchanged {
+            return;
+        }
+        entries.retain(|entry| entry["path"].as_str() != Some(path));
+        entries.insert(0, serde_json::json!({ "path": path, "svg": svg }));
+        entries.truncate(MAX_RECENT);
+
+        let stored = local_storage().and_then(|storage| {
+            let text = serde_json::to_string(&entries).ok()?;
+            storage.set_item(RECENT_KEY, &text).ok()
+        });
+        if stored.is_none() {
+            self.report_message("Failed to save recent files.", 196);
+        }
+    }
+
+    fn autosave(&self, session: &str) {
+        let stored =
+            local_storage().and_then(|storage| storage.set_item(RECOVERY_KEY, session).ok());
+        if stored.is_none() {
+            self.report_message("Failed to autosave.", 196);
+        }
+    }
+
+    fn clear_autosave(&self) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(RECOVERY_KEY);
+        }
+    }
+
+    fn recovery(&self) -> Option<String> {
+        // Saving clears the stored session, so one still there is unsaved.
+        local_storage()?.get_item(RECOVERY_KEY).ok()?
+    }
+
+    fn load_config(&self) -> Option<String> {
+        local_storage()?.get_item(CONFIG_KEY).ok()?
+    }
+
+    fn save_config(&self, text: &str) {
+        let stored = local_storage().and_then(|storage| storage.set_item(CONFIG_KEY, text).ok());
+        if stored.is_none() {
+            self.report_message("Failed to save settings.", 196);
+        }
     }
 
     fn take_pending_handle(&self) -> Option<crate::platform::FileHandle> {
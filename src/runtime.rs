@@ -1,7 +1,12 @@
 use crate::{
     app,
-    core::model::{AppPhase, State},
+    core::{
+        config::config,
+        model::{AppPhase, State},
+    },
+    features::{gallery::recent_entries, session::session_from_json},
     platform,
+    screens::gallery::ui::utils::open_gallery_in_state,
 };
 use incredible::{DeferredValue, Globals};
 
@@ -13,10 +18,28 @@ pub fn run() -> DeferredValue<State> {
     let mut initial_state = State::default();
     initial_state.flow.phase = initial_phase.clone();
     initial_state.editor.file_path = file_path.clone();
+    initial_state.flow.recent = recent_entries(io.recent_files());
 
-    Globals::set_tick_rate(10.0);
-    if initial_phase != AppPhase::Launch {
-        io.start_import(file_path);
+    Globals::set_tick_rate(config().idle_ticks as f64);
+    match initial_phase {
+        AppPhase::Launch => {}
+        AppPhase::Gallery => {
+            initial_state.flow.gallery_dir = file_path;
+            open_gallery_in_state(&mut initial_state, AppPhase::Launch);
+        }
+        _ => io.start_import(file_path),
+    }
+
+    // Work left unsaved by a previous session is offered before anything else.
+    let recovery = io.recovery().and_then(|session| {
+        let mut editor = initial_state.editor.clone();
+        session_from_json(&session, &mut editor).ok()?;
+        Some(editor)
+    });
+    if recovery.is_some() {
+        initial_state.flow.recovery = recovery;
+        initial_state.flow.recovery_return = initial_phase;
+        initial_state.flow.phase = AppPhase::Recover;
     }
 
     let app = app::build();
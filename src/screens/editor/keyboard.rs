@@ -0,0 +1,374 @@
+use super::ui::utils::*;
+use crate::core::{
+    config::{KeyAction, config},
+    model::{AppPhase, ExitFlow, Focus, State},
+};
+use crate::features::{
+    fill::fill,
+    text_stamp::{MAX_STAMP_LEN, has_glyph},
+};
+use crate::screens::gallery::ui::utils::open_gallery_in_state;
+use crate::screens::settings::ui::utils::open_settings_in_state;
+use incredible::*;
+
+/// Movement for arrow keys and their vi counterparts (dx, dy).
+fn direction(key: &Key) -> Option<(isize, isize)> {
+    match key {
+        Key::Left | Key::Char('h') => Some((-1, 0)),
+        Key::Right | Key::Char('l') => Some((1, 0)),
+        Key::Up | Key::Char('k') => Some((0, -1)),
+        Key::Down | Key::Char('j') => Some((0, 1)),
+        _ => None,
+    }
+}
+
+fn is_confirm(key: &Key) -> bool {
+    matches!(key, Key::Enter | Key::Char(' '))
+}
+
+/// A keyboard action of the layers or frames: its name and what it does.
+type Action = (&'static str, fn(&mut State));
+
+/// Layer actions, in the order of the layers panel.
+const LAYER_ACTIONS: [Action; 7] = [
+    ("show/hide", |state| {
+        let index = state.editor.active_layer;
+        state.editor.toggle_layer(index);
+    }),
+    ("add", |state| state.editor.add_layer()),
+    ("delete", |state| state.editor.delete_layer()),
+    ("move up", |state| state.editor.move_layer(true)),
+    ("move down", |state| state.editor.move_layer(false)),
+    ("merge down", |state| state.editor.merge_layer_down()),
+    ("save as layers or flat", |state| {
+        state.editor.export_layers = !state.editor.export_layers;
+    }),
+];
+
+/// Frame actions, in the order of the frames row.
+const FRAME_ACTIONS: [Action; 8] = [
+    ("previous", |state| state.editor.step_frame(false)),
+    ("next", |state| state.editor.step_frame(true)),
+    ("add", |state| state.editor.add_frame(false)),
+    ("duplicate", |state| state.editor.add_frame(true)),
+    ("delete", |state| state.editor.delete_frame()),
+    ("delay", |state| state.editor.cycle_frame_delay()),
+    ("play/stop", |state| {
+        state.editor.toggle_playback(Globals::now())
+    }),
+    ("onion skin", |state| {
+        state.editor.onion_skin = !state.editor.onion_skin;
+    }),
+];
+
+/// Label and actions of the areas run through an action list.
+fn focus_actions(focus: Focus) -> Option<(&'static str, &'static [Action])> {
+    match focus {
+        Focus::Layers => Some(("Layers", &LAYER_ACTIONS)),
+        Focus::Frames => Some(("Frames", &FRAME_ACTIONS)),
+        _ => None,
+    }
+}
+
+/// Names the chosen action in the status line.
+fn action_status(state: &mut State, focus: Focus) {
+    if let Some((label, actions)) = focus_actions(focus) {
+        let (name, _) = actions[state.editor.focus_action.min(actions.len() - 1)];
+        state.flow.status = Some(format!(
+            "{}: {} (left/right choose, Space runs)",
+            label, name
+        ));
+    }
+}
+
+fn step_focus(state: &mut State, forward: bool) {
+    let count = Focus::ALL.len();
+    let previous = state.editor.focus;
+    let next = match previous {
+        None => 0,
+        Some(focus) => {
+            let current = Focus::ALL.iter().position(|&f| f == focus).unwrap_or(0);
+            if forward {
+                (current + 1) % count
+            } else {
+                (current + count - 1) % count
+            }
+        }
+    };
+    let focus = Focus::ALL[next];
+    state.editor.focus = Some(focus);
+    state.editor.focus_action = 0;
+    if let Some(panel) = focus.panel() {
+        state.editor.panel = panel;
+    }
+    if previous.and_then(focus_actions).is_some() {
+        state.flow.status = None;
+    }
+    action_status(state, focus);
+}
+
+fn canvas_key(state: &mut State, key: &Key) {
+    let size = state.editor.size as usize;
+    let (row, col) = state.editor.cursor;
+
+    if let Some((dx, dy)) = direction(key) {
+        let col = (col as isize + dx).clamp(0, size as isize - 1) as usize;
+        let row = (row as isize + dy).clamp(0, size as isize - 1) as usize;
+        state.editor.cursor = (row, col);
+        reveal_cursor_in_state(state);
+        return;
+    }
+
+    let paintbrush = state.editor.paintbrush;
+    let config = config();
+    if is_confirm(key) {
+        state.editor.canvas_data_mut()[row * size + col] = paintbrush;
+    } else if *key == Key::Char(config.key(KeyAction::Fill)) {
+        let brush = (paintbrush, state.editor.secondary);
+        let options = state.editor.fill;
+        let data = state.editor.canvas_data_mut();
+        fill(data, size, (row, col), brush, &options);
+    } else if *key == Key::Char(config.key(KeyAction::Pick)) {
+        let color = state
+            .editor
+            .composite()
+            .get(row * size + col)
+            .copied()
+            .flatten();
+        pick_color_in_state(state, color);
+    }
+}
+
+fn picker_key(state: &mut State, key: &Key, grid: (usize, usize, usize)) {
+    if let Some(step) = direction(key) {
+        state.editor.candidate = Some(step_picker(state.editor.candidate, grid, step));
+        state.editor.picker_mode = true;
+    } else if is_confirm(key) {
+        let candidate = Some(step_picker(state.editor.candidate, grid, (0, 0)));
+        state.editor.candidate = candidate;
+        state.editor.paintbrush = candidate;
+        set_palette_in_state(state, candidate);
+    }
+}
+
+/// The 16-color column, then the transparent swatch: up and down move,
+/// `Space` / `Enter` picks.
+fn basic_key(state: &mut State, key: &Key) {
+    let row = match state.editor.candidate {
+        None => BASIC_COLORS,
+        Some(code) => (0..BASIC_COLORS)
+            .find(|&row| basic_color(row) == code)
+            .unwrap_or(0),
+    };
+    let color = |row: u8| (row < BASIC_COLORS).then(|| basic_color(row));
+    if let Some((_, dy)) = direction(key) {
+        let row = (row as isize + dy).clamp(0, BASIC_COLORS as isize) as u8;
+        state.editor.candidate = color(row);
+        state.editor.picker_mode = true;
+    } else if is_confirm(key) {
+        state.editor.candidate = color(row);
+        state.editor.paintbrush = color(row);
+        set_palette_in_state(state, color(row));
+    }
+}
+
+/// Layers and frames: left and right choose an action, `Space` / `Enter`
+/// runs it; up and down select the layer above or below.
+fn actions_key(state: &mut State, key: &Key, focus: Focus) {
+    let Some((_, actions)) = focus_actions(focus) else {
+        return;
+    };
+    let chosen = state.editor.focus_action.min(actions.len() - 1);
+    match direction(key) {
+        Some((dx, 0)) => {
+            let last = actions.len() as isize - 1;
+            state.editor.focus_action = (chosen as isize + dx).clamp(0, last) as usize;
+        }
+        Some((_, dy)) if focus == Focus::Layers => {
+            // The top layer is drawn first.
+            let last = state.editor.layers.len() as isize - 1;
+            let index = (state.editor.active_layer as isize - dy).clamp(0, last);
+            state.editor.active_layer = index as usize;
+        }
+        Some(_) => {}
+        None if is_confirm(key) => (actions[chosen].1)(state),
+        None => return,
+    }
+    action_status(state, focus);
+}
+
+/// Typing for the text stamp; returns whether `key` was taken by it.
+fn stamp_key(state: &mut State, key: &Key) -> bool {
+    let font = state.editor.stamp_font;
+    let Some(text) = state.editor.stamp.as_mut() else {
+        return false;
+    };
+    match key {
+        Key::Char(c) if has_glyph(*c, font) => {
+            if text.chars().count() < MAX_STAMP_LEN {
+                text.push(*c);
+            }
+        }
+        Key::Backspace => {
+            text.pop();
+        }
+        Key::Escape => state.editor.stamp = None,
+        Key::Enter => {
+            let cursor = state.editor.cursor;
+            stamp_in_state(state, cursor);
+        }
+        _ => return false,
+    }
+    stamp_status_in_state(state);
+    true
+}
+
+/// Whether a destructive `action` goes ahead: the first press only asks for
+/// its key again, the second one confirms.
+fn confirmed(state: &mut State, action: KeyAction) -> bool {
+    if state.flow.confirm == Some(action) {
+        state.flow.confirm = None;
+        state.flow.status = None;
+        return true;
+    }
+    state.flow.confirm = Some(action);
+    state.flow.status = Some(format!(
+        "Press {} again to {}",
+        config().key(action),
+        action.name()
+    ));
+    false
+}
+
+/// Bound shortcuts that work whatever has focus; returns whether `c` was one.
+fn action_key(state: &mut State, c: char) -> bool {
+    let action = config().action(c);
+    let unconfirmed = match action {
+        Some(action) if action.is_destructive() => !confirmed(state, action),
+        _ => false,
+    };
+    if unconfirmed {
+        return true;
+    }
+    match action {
+        Some(KeyAction::Swap) => state.editor.swap_colors(),
+        Some(KeyAction::Transparent) => {
+            state.editor.paintbrush = None;
+            set_palette_in_state(state, None);
+        }
+        Some(KeyAction::Clear) => state.editor.canvas_data_mut().fill(None),
+        Some(KeyAction::New) => start_new_in_state(state),
+        Some(KeyAction::Save) => state.editor.save_requested = true,
+        Some(KeyAction::Palette) => load_palette_file_in_state(state),
+        Some(KeyAction::Icons) => open_gallery_in_state(state, AppPhase::Main),
+        Some(KeyAction::Settings) => open_settings_in_state(state, AppPhase::Main),
+        Some(KeyAction::Exit) => state.flow.exit_flow = ExitFlow::ExitRequested,
+        // Fill and pick act on the canvas only.
+        Some(KeyAction::Fill | KeyAction::Pick) | None => return false,
+    }
+    true
+}
+
+/// Shortcuts that work whatever has focus; returns whether `key` was one.
+fn shortcut_key(state: &mut State, key: &Key) -> bool {
+    let bound = match key {
+        Key::Char(c) => action_key(state, *c),
+        _ => false,
+    };
+    if bound {
+        return true;
+    }
+    match key {
+        Key::Tab => step_focus(state, true),
+        Key::BackTab => step_focus(state, false),
+        Key::Escape => {
+            if state.editor.focus.and_then(focus_actions).is_some() {
+                state.flow.status = None;
+            }
+            state.editor.focus = None;
+        }
+        Key::Char('[') => select_palette_slot(state, false),
+        Key::Char(']') => select_palette_slot(state, true),
+        Key::Char('+') | Key::Char('=') => {
+            let zoom = stepped_zoom(state, true);
+            set_zoom_in_state(state, Some(zoom));
+        }
+        Key::Char('-') => {
+            let zoom = stepped_zoom(state, false);
+            set_zoom_in_state(state, Some(zoom));
+        }
+        Key::Char('<') => step_size_in_state(state, false),
+        Key::Char('>') => step_size_in_state(state, true),
+        Key::Char('{') => step_panel_in_state(state, false),
+        Key::Char('}') => step_panel_in_state(state, true),
+        _ => return false,
+    }
+    true
+}
+
+/// Keyboard editing in the editor.
+///
+/// - `Tab` / `Shift+Tab` — move focus between canvas, palette, 16-color column
+///   (with the transparent swatch), cube, gray ramp, layers and frames
+/// - arrows or `h` `j` `k` `l` — move within the focused area; on layers and
+///   frames, left and right choose an action and up and down select a layer
+/// - `Space` / `Enter` — paint (canvas), pick the color (pickers) or run the
+///   chosen action: show/hide, add, delete, move up or down, merge down and
+///   save as layers or flat (layers); previous, next, add, duplicate, delete,
+///   delay, play/stop and onion skin (frames)
+/// - `f` — flood fill, `p` — pick color from canvas
+/// - `[` / `]` — previous / next palette slot
+/// - `e` — transparent paintbrush, `x` — swap primary and secondary colors
+/// - `+` / `-` — zoom, `<` / `>` — smaller / larger canvas size
+/// - `{` / `}` — previous / next side panel
+/// - `c` — clear, `n` — new icon, `q` — exit (each pressed twice to confirm)
+/// - `s` — save, `i` — load the palette file, `o` — browse icons, `,` — settings
+/// - `Esc` — drop keyboard focus
+///
+/// The letter shortcuts are the defaults, rebound in the settings. Any other
+/// key cancels a confirmation.
+///
+/// While the text stamp is on, typing goes to its text instead: `Backspace`
+/// deletes, `Enter` stamps at the cursor and `Esc` cancels.
+pub(crate) fn handle_key(state: &mut State, key: &Key) {
+    let pending = state
+        .flow
+        .confirm
+        .map(|action| Key::Char(config().key(action)));
+    if pending.is_some() && pending.as_ref() != Some(key) {
+        state.flow.confirm = None;
+        state.flow.status = None;
+    }
+    if stamp_key(state, key) || shortcut_key(state, key) {
+        return;
+    }
+
+    // Moving or painting without focus starts on the canvas.
+    let focus = match state.editor.focus {
+        Some(focus) => focus,
+        None if direction(key).is_some() || is_confirm(key) => Focus::Canvas,
+        None => return,
+    };
+    state.editor.focus = Some(focus);
+
+    match focus {
+        Focus::Canvas => canvas_key(state, key),
+        Focus::Palette => match direction(key) {
+            Some((dx, _)) if dx != 0 => select_palette_slot(state, dx > 0),
+            Some((_, dy)) => {
+                // Up and down move between the two rows of the palette bar.
+                let count = state.editor.palette_colors.len() as isize;
+                let index = state.editor.palette_index as isize + dy * PALETTE_COLUMNS as isize;
+                if (0..count).contains(&index) {
+                    state.editor.palette_index = index as usize;
+                    state.editor.paintbrush = state.editor.palette_colors[index as usize];
+                }
+            }
+            None => {}
+        },
+        Focus::Basic => basic_key(state, key),
+        Focus::Cube => picker_key(state, key, CUBE),
+        Focus::Gray => picker_key(state, key, GRAY),
+        Focus::Layers | Focus::Frames => actions_key(state, key, focus),
+    }
+}
@@ -1,2 +1,3 @@
+pub(crate) mod keyboard;
 pub(crate) mod screen;
 pub(crate) mod ui;
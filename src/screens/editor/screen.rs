@@ -1,9 +1,50 @@
+use super::ui::utils::spread;
 use crate::{
     core::model::{AppPhase, State},
     ui::{APP_HEIGHT, APP_WIDTH},
 };
 use incredible::*;
-use incredible_elements::Rectangle;
+use incredible_elements::{Rectangle, Text, TextButton};
+
+/// Moves an editor element from its position for the smallest terminal into
+/// the spare cells of the current one (see `spread`).
+type Spreader = Box<dyn Fn(&State)>;
+
+/// A spreader for every element in `wrapper` so far, from the position it was
+/// built at.
+fn spreaders(wrapper: &Rectangle<State>) -> Vec<Spreader> {
+    let elements = wrapper.elements();
+    let mut spreaders: Vec<Spreader> = vec![];
+    for el in elements.cot::<TextButton<State>>() {
+        let (x, y) = (el.visual.x.get(), el.visual.y.get());
+        spreaders.push(Box::new(move |state: &State| {
+            let (x, y) = spread(state, x, y);
+            el.x(x).y(y);
+        }));
+    }
+    for el in elements.cot::<Text<State>>() {
+        let (x, y) = (el.visual.x.get(), el.visual.y.get());
+        spreaders.push(Box::new(move |state: &State| {
+            let (x, y) = spread(state, x, y);
+            el.x(x).y(y);
+        }));
+    }
+    for el in elements.cot::<Element<State>>() {
+        let (x, y) = (el.visual.x.get(), el.visual.y.get());
+        spreaders.push(Box::new(move |state: &State| {
+            let (x, y) = spread(state, x, y);
+            el.x(x).y(y);
+        }));
+    }
+    for el in elements.cot::<Rectangle<State>>() {
+        let (x, y) = (el.visual.x.get(), el.visual.y.get());
+        spreaders.push(Box::new(move |state: &State| {
+            let (x, y) = spread(state, x, y);
+            el.x(x).y(y);
+        }));
+    }
+    spreaders
+}
 
 pub fn build() -> Rectangle<State> {
     let wrapper: Rectangle<State> = Rectangle::new();
@@ -14,38 +55,119 @@ pub fn build() -> Rectangle<State> {
         .height(APP_HEIGHT.saturating_sub(1))
         .fill(Some(' '));
 
-    wrapper
-        .on_state(|el, state| {
-            el.showed(!state.flow.viewport_too_small && state.flow.phase == AppPhase::Main);
-        })
-        .on_mouse(|el, state, event| {
-            if event.mouse == Mouse::Move && el.status().hovered.get() {
-                state.editor.picker_mode = false;
-            }
-        });
-
-    wrapper.add(super::ui::canvas_8::build());
-    wrapper.add(super::ui::canvas_16::build());
+    wrapper.on_mouse(|el, state, event| {
+        // Buttons are up again, so any palette slot drag is over.
+        if event.mouse == Mouse::Move {
+            state.editor.palette_drag = None;
+        }
+        if event.mouse == Mouse::Move && el.status().hovered.get() {
+            state.editor.picker_mode = false;
+            state.editor.hover = None;
+        }
+    });
+
+    wrapper.add(super::ui::button_pan_up::build());
+    wrapper.add(super::ui::button_pan_down::build());
+    wrapper.add(super::ui::button_pan_left::build());
+    wrapper.add(super::ui::button_pan_right::build());
+
+    // Panels go before the pickers so their blank patch on hide is drawn first.
+    wrapper.add(super::ui::preview::build());
+    wrapper.add(super::ui::usage::build());
+    wrapper.add(super::ui::layers::build());
+    wrapper.add(super::ui::export::build());
 
     wrapper.add(super::ui::color_picker_16::build());
     wrapper.add(super::ui::color_picker_216::build());
     wrapper.add(super::ui::color_picker_gray::build());
     wrapper.add(super::ui::color_picker_empty::build());
     wrapper.add(super::ui::color_picker_palette::build());
+    wrapper.add(super::ui::label_slots::build());
+    wrapper.add(super::ui::button_slot_remove::build());
+    wrapper.add(super::ui::label_slot_count::build());
+    wrapper.add(super::ui::button_slot_add::build());
+    wrapper.add(super::ui::button_palette_page::build());
+    wrapper.add(super::ui::label_extract::build());
+    wrapper.add(super::ui::button_extract_frequency::build());
+    wrapper.add(super::ui::button_extract_hue::build());
+    wrapper.add(super::ui::button_palette_purge::build());
+    wrapper.add(super::ui::label_frames::build());
+    wrapper.add(super::ui::button_frame_prev::build());
+    wrapper.add(super::ui::label_frame_value::build());
+    wrapper.add(super::ui::button_frame_next::build());
+    wrapper.add(super::ui::button_frame_add::build());
+    wrapper.add(super::ui::button_frame_duplicate::build());
+    wrapper.add(super::ui::button_frame_delete::build());
+    wrapper.add(super::ui::button_frame_delay::build());
+    wrapper.add(super::ui::button_play::build());
+    wrapper.add(super::ui::button_onion_skin::build());
 
     wrapper.add(super::ui::label_color_selected::build());
     wrapper.add(super::ui::color_selected::build());
+    wrapper.add(super::ui::color_secondary::build());
     wrapper.add(super::ui::color_candidate::build());
     wrapper.add(super::ui::label_color_candidate::build());
 
-    wrapper.add(super::ui::label_new::build());
-    wrapper.add(super::ui::button_8::build());
-    wrapper.add(super::ui::button_16::build());
+    wrapper.add(super::ui::label_size::build());
+    wrapper.add(super::ui::button_size_prev::build());
+    wrapper.add(super::ui::label_size_value::build());
+    wrapper.add(super::ui::button_size_next::build());
+    wrapper.add(super::ui::label_vision::build());
+    wrapper.add(super::ui::button_vision::build());
+
+    wrapper.add(super::ui::label_edit::build());
+    wrapper.add(super::ui::button_clear::build());
+    wrapper.add(super::ui::button_palette_link::build());
+    wrapper.add(super::ui::button_snap::build());
+    wrapper.add(super::ui::button_snap_dither::build());
+
+    wrapper.add(super::ui::label_zoom::build());
+    wrapper.add(super::ui::button_zoom_out::build());
+    wrapper.add(super::ui::button_zoom_fit::build());
+    wrapper.add(super::ui::button_zoom_in::build());
+    wrapper.add(super::ui::label_session::build());
+    wrapper.add(super::ui::button_session_share::build());
+
+    wrapper.add(super::ui::label_panel::build());
+    wrapper.add(super::ui::button_panel_prev::build());
+    wrapper.add(super::ui::label_panel_value::build());
+    wrapper.add(super::ui::button_panel_next::build());
+
+    wrapper.add(super::ui::label_files::build());
+    wrapper.add(super::ui::button_gallery::build());
+    wrapper.add(super::ui::button_settings::build());
+    wrapper.add(super::ui::label_show::build());
+    wrapper.add(super::ui::button_show_checker::build());
+    wrapper.add(super::ui::button_show_grid::build());
+    wrapper.add(super::ui::button_show_guides::build());
+    wrapper.add(super::ui::button_show_crosshair::build());
+    wrapper.add(super::ui::label_palette::build());
+    wrapper.add(super::ui::button_palette_format::build());
+    wrapper.add(super::ui::button_palette_save::build());
+    wrapper.add(super::ui::label_fill::build());
+    wrapper.add(super::ui::button_fill_eight::build());
+    wrapper.add(super::ui::button_fill_global::build());
+    wrapper.add(super::ui::button_fill_gradient::build());
+    wrapper.add(super::ui::button_fill_tolerance::build());
+    wrapper.add(super::ui::label_text::build());
+    wrapper.add(super::ui::button_text_stamp::build());
+    wrapper.add(super::ui::button_text_font::build());
 
     wrapper.add(super::ui::label_end::build());
     wrapper.add(super::ui::button_save::build());
     wrapper.add(super::ui::button_exit::build());
 
+    // Everything so far spreads around the canvas, which grows into the
+    // spare cells on its own.
+    let spreaders = spreaders(&wrapper);
+    wrapper.on_state(move |el, state| {
+        el.showed(!state.flow.viewport_too_small && state.flow.phase == AppPhase::Main);
+        for spread in &spreaders {
+            spread(state);
+        }
+    });
+    wrapper.add(super::ui::canvas::build());
+
     wrapper.showed(false);
 
     wrapper
@@ -0,0 +1,26 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 67;
+static Y: isize = 4;
+
+pub fn build() -> TextButton<State> {
+    let button_clear: TextButton<State> = TextButton::default();
+    button_clear
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Clear")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                // Only the active variant is cleared, the other sizes are kept.
+                state.editor.canvas_data_mut().fill(None);
+            }
+        });
+
+    button_clear
+}
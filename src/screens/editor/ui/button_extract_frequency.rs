@@ -0,0 +1,25 @@
+use crate::core::model::{PaletteOrder, State};
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 8;
+static Y: isize = 22;
+
+pub fn build() -> TextButton<State> {
+    let button_extract_frequency: TextButton<State> = TextButton::default();
+    button_extract_frequency
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Freq")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.extract_palette(PaletteOrder::Frequency);
+            }
+        });
+
+    button_extract_frequency
+}
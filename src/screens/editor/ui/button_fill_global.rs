@@ -0,0 +1,28 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 72;
+static Y: isize = 21;
+
+pub fn build() -> TextButton<State> {
+    let button_fill_global: TextButton<State> = TextButton::default();
+    button_fill_global
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("All")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.fill.global = !state.editor.fill.global;
+            }
+        })
+        .on_state(|el, state| {
+            el.inverse(Some(state.editor.fill.global));
+        });
+
+    button_fill_global
+}
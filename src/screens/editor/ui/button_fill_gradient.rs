@@ -0,0 +1,28 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 66;
+static Y: isize = 22;
+
+pub fn build() -> TextButton<State> {
+    let button_fill_gradient: TextButton<State> = TextButton::default();
+    button_fill_gradient
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Grad")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.fill.gradient = !state.editor.fill.gradient;
+            }
+        })
+        .on_state(|el, state| {
+            el.inverse(Some(state.editor.fill.gradient));
+        });
+
+    button_fill_gradient
+}
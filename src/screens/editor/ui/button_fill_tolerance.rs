@@ -0,0 +1,34 @@
+use crate::core::model::{FILL_TOLERANCES, State};
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 72;
+static Y: isize = 22;
+
+pub fn build() -> TextButton<State> {
+    let button_fill_tolerance: TextButton<State> = TextButton::default();
+    button_fill_tolerance
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("±0 ")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                // Cycle through the tolerance steps.
+                let fill = &mut state.editor.fill;
+                let next = FILL_TOLERANCES
+                    .iter()
+                    .position(|&t| t == fill.tolerance)
+                    .map_or(0, |idx| (idx + 1) % FILL_TOLERANCES.len());
+                fill.tolerance = FILL_TOLERANCES[next];
+            }
+        })
+        .on_state(|el, state| {
+            el.text(&format!("±{:<2}", state.editor.fill.tolerance));
+        });
+
+    button_fill_tolerance
+}
@@ -3,24 +3,23 @@ use incredible::*;
 use incredible_elements::TextButton;
 use incredible_helpers_styling::*;
 
-static X: isize = 67;
-static Y: isize = 2;
+static X: isize = 20;
+static Y: isize = 23;
 
 pub fn build() -> TextButton<State> {
-    let button_8: TextButton<State> = TextButton::default();
-    button_8
+    let button_frame_add: TextButton<State> = TextButton::default();
+    button_frame_add
         .x(X)
         .y(Y)
         .focused(false)
         .pointer(Some(PointerShape::Pointer))
-        .text("8x8")
+        .text("+")
         .underline(Some(UnderlineKind::Dotted))
         .on_mouse(|_el, state, event| {
             if event.mouse == Mouse::Click {
-                state.editor.size = 8;
-                state.editor.canvas8_data = vec![None; 64];
+                state.editor.add_frame(false);
             }
         });
 
-    button_8
+    button_frame_add
 }
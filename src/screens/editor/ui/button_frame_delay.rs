@@ -0,0 +1,32 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 29;
+static Y: isize = 23;
+
+pub fn build() -> TextButton<State> {
+    let button_frame_delay: TextButton<State> = TextButton::default();
+    button_frame_delay
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text(" 100ms")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.cycle_frame_delay();
+            }
+        })
+        .on_state(|el, state| {
+            let editor = &state.editor;
+            el.text(&format!(
+                "{:>4}ms",
+                editor.frames[editor.active_frame].delay
+            ));
+        });
+
+    button_frame_delay
+}
@@ -0,0 +1,25 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 17;
+static Y: isize = 23;
+
+pub fn build() -> TextButton<State> {
+    let button_frame_next: TextButton<State> = TextButton::default();
+    button_frame_next
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("▶")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.step_frame(true);
+            }
+        });
+
+    button_frame_next
+}
@@ -0,0 +1,26 @@
+use crate::core::model::{AppPhase, State};
+use crate::screens::gallery::ui::utils::open_gallery_in_state;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 66;
+static Y: isize = 16;
+
+pub fn build() -> TextButton<State> {
+    let button_gallery: TextButton<State> = TextButton::default();
+    button_gallery
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Icons")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                open_gallery_in_state(state, AppPhase::Main);
+            }
+        });
+
+    button_gallery
+}
@@ -0,0 +1,28 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 42;
+static Y: isize = 23;
+
+pub fn build() -> TextButton<State> {
+    let button_onion_skin: TextButton<State> = TextButton::default();
+    button_onion_skin
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Onion")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.onion_skin = !state.editor.onion_skin;
+            }
+        })
+        .on_state(|el, state| {
+            el.inverse(Some(state.editor.onion_skin));
+        });
+
+    button_onion_skin
+}
@@ -0,0 +1,34 @@
+use crate::core::model::{PaletteFormat, State};
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 66;
+static Y: isize = 20;
+
+pub fn build() -> TextButton<State> {
+    let button_palette_format: TextButton<State> = TextButton::default();
+    button_palette_format
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text(".gpl ")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                // Cycle through the palette file formats.
+                let all = PaletteFormat::ALL;
+                let current = all
+                    .iter()
+                    .position(|&f| f == state.editor.palette_format)
+                    .unwrap_or(0);
+                state.editor.palette_format = all[(current + 1) % all.len()];
+            }
+        })
+        .on_state(|el, state| {
+            el.text(&format!(".{:<4}", state.editor.palette_format.extension()));
+        });
+
+    button_palette_format
+}
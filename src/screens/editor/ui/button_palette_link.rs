@@ -0,0 +1,28 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 73;
+static Y: isize = 4;
+
+pub fn build() -> TextButton<State> {
+    let button_palette_link: TextButton<State> = TextButton::default();
+    button_palette_link
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Link")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.linked_palette = !state.editor.linked_palette;
+            }
+        })
+        .on_state(|el, state| {
+            el.inverse(Some(state.editor.linked_palette));
+        });
+
+    button_palette_link
+}
@@ -0,0 +1,32 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 15;
+static Y: isize = 21;
+
+pub fn build() -> TextButton<State> {
+    let button_palette_page: TextButton<State> = TextButton::default();
+    button_palette_page
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("1/1")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                step_palette_page_in_state(state);
+            }
+        })
+        .on_state(|el, state| {
+            // Current page of the palette bar out of all pages.
+            let pages = state.editor.palette_colors.len().div_ceil(PALETTE_PAGE);
+            let page = palette_page_start(state) / PALETTE_PAGE + 1;
+            el.text(&format!("{}/{}", page, pages));
+        });
+
+    button_palette_page
+}
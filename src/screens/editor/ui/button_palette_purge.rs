@@ -0,0 +1,25 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 17;
+static Y: isize = 22;
+
+pub fn build() -> TextButton<State> {
+    let button_palette_purge: TextButton<State> = TextButton::default();
+    button_palette_purge
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Purge")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.purge_palette();
+            }
+        });
+
+    button_palette_purge
+}
@@ -0,0 +1,35 @@
+use crate::core::model::State;
+use crate::features::palette_file::{build_palette_file, palette_file_name};
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+use std::path::Path;
+
+static X: isize = 72;
+static Y: isize = 20;
+
+pub fn build() -> TextButton<State> {
+    let button_palette_save: TextButton<State> = TextButton::default();
+    button_palette_save
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Export")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                let format = state.editor.palette_format;
+                let file_name = palette_file_name(&state.editor.file_path, format);
+                let name = Path::new(&state.editor.file_path)
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("Rusticon")
+                    .to_string();
+                let content = build_palette_file(format, &name, &state.editor.palette_colors);
+                save_files_in_state(state, vec![(file_name, content.into_bytes())]);
+            }
+        });
+
+    button_palette_save
+}
@@ -0,0 +1,28 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 38;
+static Y: isize = 19;
+
+pub fn build() -> TextButton<State> {
+    let button_pan_down: TextButton<State> = TextButton::default();
+    button_pan_down
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text(" ")
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                pan_in_state(state, PanDirection::Down);
+            }
+        })
+        .on_state(|el, state| {
+            el.text(pan_arrow(state, PanDirection::Down));
+        });
+
+    button_pan_down
+}
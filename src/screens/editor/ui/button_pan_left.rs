@@ -0,0 +1,28 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 21;
+static Y: isize = 10;
+
+pub fn build() -> TextButton<State> {
+    let button_pan_left: TextButton<State> = TextButton::default();
+    button_pan_left
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text(" ")
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                pan_in_state(state, PanDirection::Left);
+            }
+        })
+        .on_state(|el, state| {
+            el.text(pan_arrow(state, PanDirection::Left));
+        });
+
+    button_pan_left
+}
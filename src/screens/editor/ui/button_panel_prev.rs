@@ -0,0 +1,25 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 66;
+static Y: isize = 15;
+
+pub fn build() -> TextButton<State> {
+    let button_panel_prev: TextButton<State> = TextButton::default();
+    button_panel_prev
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("◀")
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                step_panel_in_state(state, false);
+            }
+        });
+
+    button_panel_prev
+}
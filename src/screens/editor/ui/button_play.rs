@@ -0,0 +1,33 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 36;
+static Y: isize = 23;
+
+pub fn build() -> TextButton<State> {
+    let button_play: TextButton<State> = TextButton::default();
+    button_play
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Play")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.toggle_playback(Globals::now());
+            }
+        })
+        .on_state(|el, state| {
+            let label = if state.editor.playing.is_some() {
+                "Stop"
+            } else {
+                "Play"
+            };
+            el.text(label);
+        });
+
+    button_play
+}
@@ -0,0 +1,29 @@
+use super::utils::*;
+use crate::core::model::State;
+use crate::features::session::{session_file_name, session_to_json};
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 66;
+static Y: isize = 7;
+
+pub fn build() -> TextButton<State> {
+    let button_session_share: TextButton<State> = TextButton::default();
+    button_session_share
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Share")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                let file_name = session_file_name(&state.editor.file_path);
+                let content = session_to_json(&state.editor);
+                save_files_in_state(state, vec![(file_name, content.into_bytes())]);
+            }
+        });
+
+    button_session_share
+}
@@ -0,0 +1,26 @@
+use crate::core::model::{AppPhase, State};
+use crate::screens::settings::ui::utils::open_settings_in_state;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 72;
+static Y: isize = 16;
+
+pub fn build() -> TextButton<State> {
+    let button_settings: TextButton<State> = TextButton::default();
+    button_settings
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Settings")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                open_settings_in_state(state, AppPhase::Main);
+            }
+        });
+
+    button_settings
+}
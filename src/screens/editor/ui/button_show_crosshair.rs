@@ -0,0 +1,28 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 72;
+static Y: isize = 18;
+
+pub fn build() -> TextButton<State> {
+    let button_show_crosshair: TextButton<State> = TextButton::default();
+    button_show_crosshair
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Cross")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.view.crosshair = !state.editor.view.crosshair;
+            }
+        })
+        .on_state(|el, state| {
+            el.inverse(Some(state.editor.view.crosshair));
+        });
+
+    button_show_crosshair
+}
@@ -0,0 +1,28 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 72;
+static Y: isize = 17;
+
+pub fn build() -> TextButton<State> {
+    let button_show_grid: TextButton<State> = TextButton::default();
+    button_show_grid
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Grid")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.view.grid = !state.editor.view.grid;
+            }
+        })
+        .on_state(|el, state| {
+            el.inverse(Some(state.editor.view.grid));
+        });
+
+    button_show_grid
+}
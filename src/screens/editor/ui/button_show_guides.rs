@@ -0,0 +1,28 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 66;
+static Y: isize = 18;
+
+pub fn build() -> TextButton<State> {
+    let button_show_guides: TextButton<State> = TextButton::default();
+    button_show_guides
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Guide")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.view.guides = !state.editor.view.guides;
+            }
+        })
+        .on_state(|el, state| {
+            el.inverse(Some(state.editor.view.guides));
+        });
+
+    button_show_guides
+}
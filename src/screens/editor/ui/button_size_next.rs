@@ -0,0 +1,25 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 74;
+static Y: isize = 2;
+
+pub fn build() -> TextButton<State> {
+    let button_size_next: TextButton<State> = TextButton::default();
+    button_size_next
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("▶")
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                step_size_in_state(state, true);
+            }
+        });
+
+    button_size_next
+}
@@ -0,0 +1,25 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 13;
+static Y: isize = 21;
+
+pub fn build() -> TextButton<State> {
+    let button_slot_add: TextButton<State> = TextButton::default();
+    button_slot_add
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("+")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.add_palette_slot();
+            }
+        });
+
+    button_slot_add
+}
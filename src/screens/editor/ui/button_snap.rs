@@ -0,0 +1,26 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 66;
+static Y: isize = 5;
+
+pub fn build() -> TextButton<State> {
+    let button_snap: TextButton<State> = TextButton::default();
+    button_snap
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Snap")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                snap_to_palette_in_state(state);
+            }
+        });
+
+    button_snap
+}
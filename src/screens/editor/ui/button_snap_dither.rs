@@ -0,0 +1,28 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 72;
+static Y: isize = 5;
+
+pub fn build() -> TextButton<State> {
+    let button_snap_dither: TextButton<State> = TextButton::default();
+    button_snap_dither
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Dither")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.snap_dither = !state.editor.snap_dither;
+            }
+        })
+        .on_state(|el, state| {
+            el.inverse(Some(state.editor.snap_dither));
+        });
+
+    button_snap_dither
+}
@@ -0,0 +1,35 @@
+use super::utils::*;
+use crate::core::model::{StampFont, State};
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 72;
+static Y: isize = 23;
+
+pub fn build() -> TextButton<State> {
+    let button_text_font: TextButton<State> = TextButton::default();
+    button_text_font
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text(StampFont::Small.label())
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                let font = state.editor.stamp_font;
+                let next = StampFont::ALL
+                    .iter()
+                    .position(|&f| f == font)
+                    .map_or(0, |idx| (idx + 1) % StampFont::ALL.len());
+                state.editor.stamp_font = StampFont::ALL[next];
+                stamp_status_in_state(state);
+            }
+        })
+        .on_state(|el, state| {
+            el.text(state.editor.stamp_font.label());
+        });
+
+    button_text_font
+}
@@ -0,0 +1,29 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 66;
+static Y: isize = 23;
+
+pub fn build() -> TextButton<State> {
+    let button_text_stamp: TextButton<State> = TextButton::default();
+    button_text_stamp
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Type")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                toggle_stamp_in_state(state);
+            }
+        })
+        .on_state(|el, state| {
+            el.inverse(Some(state.editor.stamp.is_some()));
+        });
+
+    button_text_stamp
+}
@@ -0,0 +1,35 @@
+use crate::core::model::{Panel, State, Vision};
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 66;
+static Y: isize = 3;
+
+pub fn build() -> TextButton<State> {
+    let button_vision: TextButton<State> = TextButton::default();
+    button_vision
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Normal")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                // Cycle the simulated color vision and bring up the preview.
+                let all = Vision::ALL;
+                let current = all
+                    .iter()
+                    .position(|&v| v == state.editor.vision)
+                    .unwrap_or(0);
+                state.editor.vision = all[(current + 1) % all.len()];
+                state.editor.panel = Panel::Preview;
+            }
+        })
+        .on_state(|el, state| {
+            el.text(&format!("{:<6}", state.editor.vision.label()));
+        });
+
+    button_vision
+}
@@ -0,0 +1,34 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 68;
+static Y: isize = 6;
+
+pub fn build() -> TextButton<State> {
+    let button_zoom_fit: TextButton<State> = TextButton::default();
+    button_zoom_fit
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text(" fit ")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                set_zoom_in_state(state, None);
+            }
+        })
+        .on_state(|el, state| {
+            // Shows the manual zoom level, or "fit" while zoom follows the size.
+            let text = match state.editor.zoom {
+                Some(zoom) => format!("{}x", zoom),
+                None => "fit".to_string(),
+            };
+            el.text(&format!("{:^5}", text));
+        });
+
+    button_zoom_fit
+}
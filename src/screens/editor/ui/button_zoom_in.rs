@@ -0,0 +1,26 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 74;
+static Y: isize = 6;
+
+pub fn build() -> TextButton<State> {
+    let button_zoom_in: TextButton<State> = TextButton::default();
+    button_zoom_in
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("+")
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                let zoom = stepped_zoom(state, true);
+                set_zoom_in_state(state, Some(zoom));
+            }
+        });
+
+    button_zoom_in
+}
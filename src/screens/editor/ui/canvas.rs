@@ -0,0 +1,108 @@
+use super::utils::*;
+use crate::core::model::AppPhase;
+use crate::core::model::State;
+use incredible::*;
+use incredible_helpers_styling::*;
+
+static X: isize = VIEW_X;
+static Y: isize = VIEW_Y;
+
+pub fn build() -> Element<State> {
+    let canvas: Element<State> = Element::new();
+    canvas
+        .x(X)
+        .y(Y)
+        .pointer(Some(PointerShape::Crosshair))
+        .look(Look::from((VIEW_COLS, VIEW_ROWS, ' ')))
+        .on_mouse(|el, state, event| {
+            // If mouse moves, it will not be a double click fill.
+            if event.mouse == Mouse::Move {
+                state.editor.prev_color_on_canvas = None;
+            }
+            let view = CanvasView::from_state(state);
+            state.editor.hover = view.cell_at(el, event.x, event.y);
+
+            // Ctrl + wheel zooms.
+            if let Some(delta) = scroll_step(&event.mouse) {
+                if event.modifiers.contains(&KeyMod::Ctrl) {
+                    let zoom = stepped_zoom(state, delta < 0);
+                    set_zoom_in_state(state, Some(zoom));
+                }
+                return;
+            }
+
+            let is_paint = event.mouse == Mouse::Down || event.mouse == Mouse::Drag;
+            let is_fill = event.mouse == Mouse::DoubleClick;
+            if !(is_paint || is_fill) {
+                return;
+            }
+
+            let size = view.size;
+            let Some((row, col)) = state.editor.hover else {
+                return;
+            };
+
+            let is_pick =
+                event.modifiers.contains(&KeyMod::Ctrl) || event.button == MouseButton::Middle;
+            if state.editor.stamp.is_some() {
+                // The text tool places its stamp instead of painting.
+                if event.mouse == Mouse::Down {
+                    stamp_in_state(state, (row, col));
+                }
+            } else if is_pick {
+                // Handle ctrl-click and middle-click for color picking (not on double-click fill)
+                if is_paint {
+                    let color = state
+                        .editor
+                        .composite()
+                        .get(row * size + col)
+                        .copied()
+                        .flatten();
+                    pick_color_in_state(state, color);
+                }
+            } else {
+                // Right button paints with the secondary color (and blends
+                // gradients back towards the primary).
+                let (primary, secondary) = (state.editor.paintbrush, state.editor.secondary);
+                let brush = if event.button == MouseButton::Right {
+                    (secondary, primary)
+                } else {
+                    (primary, secondary)
+                };
+                let fill_options = state.editor.fill;
+                let mut prev_color_on_canvas = state.editor.prev_color_on_canvas;
+                canvas_data_from_cell(
+                    size,
+                    state.editor.canvas_data_mut(),
+                    brush,
+                    row,
+                    col,
+                    &mut prev_color_on_canvas,
+                    is_fill.then_some(&fill_options),
+                );
+                state.editor.prev_color_on_canvas = prev_color_on_canvas;
+            }
+
+            let look =
+                canvas_look_from_data(&view, &canvas_display_data(&state.editor), &state.editor);
+            el.look(look);
+        })
+        .on_state(|el, state| {
+            let active = state.flow.phase == AppPhase::Main;
+            el.showed(active);
+            if !active {
+                return;
+            }
+
+            // The viewport grows evenly into the spare cells.
+            let (spare_cols, spare_rows) = state.flow.spare;
+            el.x(X - spare_cols as isize).y(Y - spare_rows as isize);
+            let view = CanvasView::from_state(state);
+            let look =
+                canvas_look_from_data(&view, &canvas_display_data(&state.editor), &state.editor);
+            el.look(look);
+            el.draw();
+        });
+
+    canvas
+}
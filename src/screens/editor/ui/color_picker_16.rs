@@ -1,45 +1,33 @@
 use super::utils::*;
-use crate::core::model::State;
+use crate::core::model::{Focus, Panel, State};
 use incredible::*;
 use incredible_helpers_styling::*;
 
 static X: isize = 1;
 static Y: isize = 2;
 
+fn picker_look(marked: Option<u8>) -> Look {
+    Look::from(
+        (0..BASIC_COLORS)
+            .map(|row| {
+                let ansi_code = basic_color(row);
+                vec![swatch_block(ansi_code, marked == Some(ansi_code))]
+            })
+            .collect::<Vec<Vec<Block>>>(),
+    )
+}
+
 pub fn build() -> Element<State> {
     let color_picker_16: Element<State> = Element::new();
     color_picker_16
         .x(X)
         .y(Y)
         .pointer(Some(PointerShape::Crosshair))
-        .look(Look::from(
-            (0..16)
-                .map(|row| {
-                    (0..1)
-                        .map(|_col| {
-                            let ansi_code: u8 =
-                                Colors::rgb_to_ansi8(Platform::ansi_color_default(row));
-                            let decor = Decor::new(
-                                Some(false),
-                                Some(false),
-                                None,
-                                None,
-                                Some(false),
-                                Some(false),
-                                Some(false),
-                                None,
-                                Some(Color::Ansi(ansi_code)),
-                            );
-                            Block::new(' ', decor)
-                        })
-                        .collect::<Vec<Block>>()
-                })
-                .collect::<Vec<Vec<Block>>>(),
-        ))
+        .look(picker_look(None))
         .on_mouse(|el, state, event| {
             if event.mouse == Mouse::Move || event.mouse == Mouse::Click {
                 let row = event.y.saturating_sub(el.visual.y.get()) as u8;
-                let ansi_code: u8 = Colors::rgb_to_ansi8(Platform::ansi_color_default(row));
+                let ansi_code = basic_color(row);
                 state.editor.candidate = Some(ansi_code);
 
                 if event.mouse == Mouse::Move {
@@ -51,8 +39,14 @@ pub fn build() -> Element<State> {
                 }
             }
         })
-        .on_state(|el, _state| {
-            el.draw();
+        .on_state(|el, state| {
+            let active = state.editor.panel == Panel::Colors;
+            el.showed(active);
+            if active {
+                let focused = state.editor.focus == Some(Focus::Basic);
+                el.look(picker_look(state.editor.candidate.filter(|_| focused)));
+                el.draw();
+            }
         });
 
     color_picker_16
@@ -1,42 +1,39 @@
 use super::utils::*;
-use crate::core::model::State;
+use crate::core::model::{Focus, Panel, State};
 use incredible::*;
 use incredible_helpers_styling::*;
 
 static X: isize = 3;
 static Y: isize = 2;
 
+fn picker_look(marked: Option<u8>) -> Look {
+    Look::from(
+        (0..18)
+            .map(|row| {
+                (0..12)
+                    .map(|col| {
+                        // original crumb formula: (row * 12) + (col * 16)
+                        let ansi_code = (row * 12 + col + 16).min(231) as u8;
+                        swatch_block(ansi_code, marked == Some(ansi_code))
+                    })
+                    .collect::<Vec<Block>>()
+            })
+            .collect::<Vec<Vec<Block>>>(),
+    )
+}
+
 pub fn build() -> Element<State> {
     let color_picker_216: Element<State> = Element::new();
     color_picker_216
         .x(X)
         .y(Y)
         .pointer(Some(PointerShape::Crosshair))
-        .look(Look::from(
-            (0..18)
-                .map(|row| {
-                    (0..12)
-                        .map(|col| {
-                            // original crumb formula: (row * 12) + (col * 16)
-                            let ansi_code = (row * 12 + col + 16).min(231) as u8;
-                            let decor = Decor::new(
-                                Some(false),
-                                Some(false),
-                                None,
-                                None,
-                                Some(false),
-                                Some(false),
-                                Some(false),
-                                None,
-                                Some(Color::Ansi(ansi_code)),
-                            );
-                            Block::new(' ', decor)
-                        })
-                        .collect::<Vec<Block>>()
-                })
-                .collect::<Vec<Vec<Block>>>(),
-        ))
+        .look(picker_look(None))
         .on_mouse(|el, state, event| {
+            if let Some(delta) = scroll_step(&event.mouse) {
+                scroll_picker_in_state(state, CUBE, Focus::Cube, delta);
+                return;
+            }
             if event.mouse == Mouse::Move || event.mouse == Mouse::Click {
                 let row = event.y.saturating_sub(el.visual.y.get()) as u8;
                 let col = event.x.saturating_sub(el.visual.x.get()) as u8;
@@ -52,8 +49,14 @@ pub fn build() -> Element<State> {
                 }
             }
         })
-        .on_state(|el, _state| {
-            el.draw();
+        .on_state(|el, state| {
+            let active = state.editor.panel == Panel::Colors;
+            el.showed(active);
+            if active {
+                let focused = state.editor.focus == Some(Focus::Cube);
+                el.look(picker_look(state.editor.candidate.filter(|_| focused)));
+                el.draw();
+            }
         });
 
     color_picker_216
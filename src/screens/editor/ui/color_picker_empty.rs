@@ -1,5 +1,6 @@
 use super::utils::*;
-use crate::core::model::State;
+use crate::core::model::{Focus, Panel, State};
+use crate::ui::pixels::void_block;
 use incredible::*;
 use incredible_helpers_styling::*;
 
@@ -26,8 +27,26 @@ pub fn build() -> Element<State> {
                 }
             }
         })
-        .on_state(|el, _state| {
-            el.draw();
+        .on_state(|el, state| {
+            let active = state.editor.panel == Panel::Colors;
+            el.showed(active);
+            if active {
+                let checkerboard = state.editor.view.checkerboard;
+                let marked =
+                    state.editor.focus == Some(Focus::Basic) && state.editor.candidate.is_none();
+                let look: Vec<Vec<Block>> = (0..2)
+                    .map(|row| {
+                        (0..2)
+                            .map(|col| match (row, col) {
+                                (0, 0) if marked => Block::new('+', Decor::default()),
+                                _ => void_block(checkerboard, row + col),
+                            })
+                            .collect()
+                    })
+                    .collect();
+                el.look(Look::from(look));
+                el.draw();
+            }
         });
 
     color_picker_empty
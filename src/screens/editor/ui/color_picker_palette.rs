@@ -1,4 +1,6 @@
-use crate::core::model::State;
+use super::utils::*;
+use crate::core::model::{Focus, State};
+use crate::ui::pixels::{CHECKER, color_decor};
 use incredible::*;
 use incredible_helpers_styling::*;
 
@@ -11,25 +13,31 @@ pub fn build<'a>() -> Element<State> {
         .x(X)
         .y(Y)
         .pointer(Some(PointerShape::Crosshair))
-        .look({
-            let row: Vec<char> = (0..32)
-                .map(|index| {
-                    if index % 4 == 1 || index % 4 == 2 {
-                        ':'
-                    } else {
-                        ' '
-                    }
-                })
-                .collect();
-            Look::from(vec![row])
-        })
+        .look(Look::from((32, 2, ' ')))
         .on_mouse(|el, state, event| {
+            if let Some(delta) = scroll_step(&event.mouse) {
+                select_palette_slot(state, delta > 0);
+                state.editor.candidate = state.editor.paintbrush;
+                state.editor.picker_mode = true;
+                return;
+            }
+
             let col_rel = event.x.saturating_sub(el.visual.x.get()) as usize;
-            let selected = if col_rel % 4 == 1 || col_rel % 4 == 2 {
-                col_rel / 4
-            } else {
-                state.editor.palette_index
-            };
+            let row_rel = event.y.saturating_sub(el.visual.y.get()) as usize;
+            let hovered = palette_slot_at(state, col_rel, row_rel);
+            let selected = hovered.unwrap_or(state.editor.palette_index);
+
+            // Dragging a slot reorders the palette, Ctrl-drag swaps two slots.
+            if event.mouse == Mouse::Down {
+                state.editor.palette_drag = hovered.map(|slot| (slot, slot));
+            }
+            if event.mouse == Mouse::Drag {
+                let swap = event.modifiers.contains(&KeyMod::Ctrl);
+                let target = palette_page_start(state)
+                    + row_rel.min(1) * PALETTE_COLUMNS
+                    + (col_rel / 4).min(PALETTE_COLUMNS - 1);
+                drag_palette_slot_in_state(state, target, swap);
+            }
 
             if event.mouse == Mouse::Click {
                 if selected < state.editor.palette_colors.len() {
@@ -38,6 +46,7 @@ pub fn build<'a>() -> Element<State> {
                 }
             }
             if event.mouse == Mouse::Move {
+                state.editor.palette_drag = None;
                 if selected < state.editor.palette_colors.len() {
                     state.editor.candidate = state.editor.palette_colors[selected];
                     state.editor.picker_mode = true;
@@ -47,43 +56,47 @@ pub fn build<'a>() -> Element<State> {
         .on_state(|el, state| {
             let pl = state.editor.palette_index;
             let pll = &state.editor.palette_colors;
+            let page_start = palette_page_start(state);
+            let focused = state.editor.focus == Some(Focus::Palette);
+            let checkerboard = state.editor.view.checkerboard;
 
-            let mut look = el.visual.look.blocks().to_vec();
+            let look: Vec<Vec<Block>> = (0..2)
+                .map(|row_i| {
+                    (0..PALETTE_COLUMNS * 4)
+                        .map(|col_i| {
+                            let palette_idx = page_start + row_i * PALETTE_COLUMNS + col_i / 4;
+                            if palette_idx >= pll.len() {
+                                return Block::new(' ', Decor::default());
+                            }
 
-            for row in look.iter_mut() {
-                for (col_i, col) in row.iter_mut().enumerate() {
-                    if col_i % 4 == 1 || col_i % 4 == 2 {
-                        let palette_idx = (col_i / 4).min(pll.len().saturating_sub(1));
-                        let coloring = pll[palette_idx];
-                        let active = col_i == pl * 4 + 1 || col_i == pl * 4 + 2;
-
-                        let mut decor = Decor::default();
-                        if let Some(ansi_code) = coloring {
-                            decor = Decor::new(
-                                Some(false),
-                                Some(false),
-                                None,
-                                None,
-                                Some(false),
-                                Some(false),
-                                Some(false),
-                                None,
-                                Some(Color::Ansi(ansi_code)),
-                            );
-                        }
-
-                        let content = if active {
-                            '+'
-                        } else {
-                            if coloring.is_none() { ':' } else { ' ' }
-                        };
-
-                        *col = Block::new(content, decor);
-                    } else {
-                        *col = Block::new(' ', Decor::default());
-                    }
-                }
-            }
+                            if col_i % 4 == 1 || col_i % 4 == 2 {
+                                let content = if palette_idx == pl { '+' } else { ' ' };
+                                match pll[palette_idx] {
+                                    Some(ansi_code) => {
+                                        Block::new(content, color_decor(None, Some(ansi_code)))
+                                    }
+                                    None if checkerboard => Block::new(
+                                        content,
+                                        color_decor(None, Some(CHECKER[col_i % 2])),
+                                    ),
+                                    None if palette_idx == pl => {
+                                        Block::new(content, Decor::default())
+                                    }
+                                    None => Block::new(':', Decor::default()),
+                                }
+                            } else {
+                                // Keyboard focus brackets the active slot.
+                                let content = match col_i % 4 {
+                                    0 if focused && palette_idx == pl => '[',
+                                    3 if focused && palette_idx == pl => ']',
+                                    _ => ' ',
+                                };
+                                Block::new(content, Decor::default())
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
 
             el.look(Look::from(look));
             el.draw();
@@ -0,0 +1,36 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Rectangle;
+use incredible_helpers_styling::*;
+
+static X: isize = 73;
+static Y: isize = 9;
+
+pub fn build() -> Rectangle<State> {
+    let color_secondary: Rectangle<State> = Rectangle::new();
+    color_secondary
+        .x(X)
+        .y(Y)
+        .width(3)
+        .height(2)
+        .fill(Some(' '))
+        .pointer(Some(PointerShape::Pointer))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.editor.swap_colors();
+            }
+        })
+        .on_state(|el, state| {
+            if let Some(bg) = state.editor.secondary {
+                el.background(Some(Color::Ansi(bg)));
+                el.fill(Some(' '));
+            } else {
+                // Transparent secondary (the default eraser) is drawn like void cells.
+                el.background(None);
+                el.fill(Some(':'));
+            }
+            el.draw();
+        });
+
+    color_secondary
+}
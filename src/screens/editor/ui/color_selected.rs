@@ -11,7 +11,7 @@ pub fn build() -> Rectangle<State> {
     color_selected
         .x(X)
         .y(Y)
-        .width(15)
+        .width(11)
         .height(2)
         .fill(Some(' '))
         .on_state(|el, state| {
@@ -0,0 +1,164 @@
+use super::utils::*;
+use crate::core::config::{EXPORT_PIXEL_SIZES, config};
+use crate::core::model::{
+    AppPhase, EXPORT_PADDINGS, EXPORT_RADII, ExportOptions, ExportProfile, Panel, State,
+};
+use crate::ui::pixels::color_decor;
+use incredible::*;
+
+/// Column the values start at, after their labels.
+const VALUE_COL: usize = 9;
+/// Rows of the settings, clicked to step through their values.
+const PROFILE_ROW: usize = 2;
+const PIXEL_ROW: usize = 3;
+const FILL_ROW: usize = 4;
+const CORNERS_ROW: usize = 5;
+const PADDING_ROW: usize = 6;
+const SIZE_ROW: usize = 8;
+/// Rows of the files saved on click next to the icon, besides the SVG.
+const ICON_SET_ROW: usize = 10;
+const ANIMATION_ROW: usize = 11;
+/// Text color of the values, on the theme's highlight.
+const VALUE_TEXT: u8 = 16;
+
+/// The entry after `current` in `options`, wrapping.
+fn next<T: Copy + PartialEq>(options: &[T], current: T) -> T {
+    let index = options.iter().position(|&o| o == current).unwrap_or(0);
+    options[(index + 1) % options.len()]
+}
+
+/// Steps the setting on `row`. Anything but the profile itself makes the
+/// options custom.
+fn step_export(options: &mut ExportOptions, row: usize, color: Option<u8>) {
+    match row {
+        PROFILE_ROW => {
+            *options = next(&ExportProfile::ALL, options.profile).options(options);
+            return;
+        }
+        // Scalable first, then every pixel size.
+        PIXEL_ROW if options.view_box => {
+            options.view_box = false;
+            options.px = EXPORT_PIXEL_SIZES[0];
+        }
+        PIXEL_ROW if options.px == EXPORT_PIXEL_SIZES[EXPORT_PIXEL_SIZES.len() - 1] => {
+            options.view_box = true;
+        }
+        PIXEL_ROW => options.px = next(&EXPORT_PIXEL_SIZES, options.px),
+        FILL_ROW if options.background.is_some() => options.background = None,
+        FILL_ROW => options.background = color,
+        CORNERS_ROW => options.radius = next(&EXPORT_RADII, options.radius),
+        PADDING_ROW => options.padding = next(&EXPORT_PADDINGS, options.padding),
+        _ => return,
+    }
+    options.profile = ExportProfile::Custom;
+}
+
+fn export_look(state: &State) -> Look {
+    let mut rows = vec![vec![Block::new(' ', Decor::default()); PANEL_COLS]; PANEL_ROWS];
+    let options = &state.editor.export;
+    let value = color_decor(Some(VALUE_TEXT), Some(config().highlight));
+
+    put_text(&mut rows[0], 0, "Export SVG", Decor::default());
+
+    let pixel = if options.view_box {
+        "scalable".to_string()
+    } else {
+        format!("{} px", options.px)
+    };
+    let fill = match options.background {
+        Some(code) => format!("   {}", code),
+        None => "none".to_string(),
+    };
+    let settings = [
+        (PROFILE_ROW, "Profile", options.profile.label().to_string()),
+        (PIXEL_ROW, "Pixel", pixel),
+        (FILL_ROW, "Fill", fill),
+        (CORNERS_ROW, "Corners", format!("{}%", options.radius)),
+        (PADDING_ROW, "Padding", options.padding.to_string()),
+    ];
+    for (row, label, text) in settings {
+        put_text(&mut rows[row], 0, label, Decor::default());
+        put_text(&mut rows[row], VALUE_COL, &text, value.clone());
+    }
+    if let Some(code) = options.background {
+        put_text(
+            &mut rows[FILL_ROW],
+            VALUE_COL,
+            "  ",
+            color_decor(None, Some(code)),
+        );
+    }
+
+    let side = state.editor.size as usize + 2 * options.padding as usize;
+    let size = if options.view_box {
+        format!("{}x{} units", side, side)
+    } else {
+        format!("{}x{} px", side * options.px, side * options.px)
+    };
+    put_text(&mut rows[SIZE_ROW], 0, "Size", Decor::default());
+    put_text(&mut rows[SIZE_ROW], VALUE_COL, &size, Decor::default());
+
+    put_text(&mut rows[ICON_SET_ROW], 0, "Icon set", Decor::default());
+    put_text(
+        &mut rows[ICON_SET_ROW],
+        VALUE_COL,
+        "ICO+HTML",
+        value.clone(),
+    );
+
+    put_text(
+        &mut rows[PANEL_ROWS - 2],
+        0,
+        "Click to change;",
+        Decor::default(),
+    );
+    put_text(
+        &mut rows[PANEL_ROWS - 1],
+        0,
+        "fill uses the color",
+        Decor::default(),
+    );
+
+    Look::from(rows)
+}
+
+pub fn build() -> Element<State> {
+    let export: Element<State> = Element::new();
+    export
+        .x(PANEL_X)
+        .y(PANEL_Y)
+        .look(Look::from((PANEL_COLS, PANEL_ROWS, ' ')))
+        .on_mouse(|el, state, event| {
+            if event.mouse != Mouse::Click || state.editor.panel != Panel::Export {
+                return;
+            }
+            let row = event.y.saturating_sub(el.visual.y.get()) as usize;
+            match row {
+                ICON_SET_ROW => export_icon_set_in_state(state),
+                ANIMATION_ROW => export_animation_in_state(state),
+                _ => {
+                    let color = state.editor.paintbrush;
+                    step_export(&mut state.editor.export, row, color);
+                }
+            }
+        })
+        .on_state(|el, state| {
+            let active = state.flow.phase == AppPhase::Main && state.editor.panel == Panel::Export;
+            if active {
+                el.showed(true);
+                el.look(export_look(state));
+                el.draw();
+                return;
+            }
+
+            // On hide, draw a blank patch once at the same location to clear stale pixels.
+            if el.get_showed() {
+                el.showed(true);
+                el.look(Look::from((PANEL_COLS, PANEL_ROWS, ' ')));
+                el.draw();
+            }
+            el.showed(false);
+        });
+
+    export
+}
@@ -17,6 +17,8 @@ pub fn build() -> Text<State> {
                     Some(c) => format!("{:<3}   {}", c, Colors::ansi8_to_hex(c)),
                     None => format!("{:<13}", ":transparent:"),
                 }
+            } else if let Some((row, col)) = state.editor.hover {
+                format!("{:<13}", format!("x:{} y:{}", col, row))
             } else {
                 "             ".to_string()
             };
@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 59;
+static Y: isize = 4;
+
+pub fn build() -> Text<State> {
+    let label_edit: Text<State> = Text::default();
+    label_edit.x(X).y(Y).text("Edit:");
+
+    label_edit
+}
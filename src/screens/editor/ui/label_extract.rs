@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 1;
+static Y: isize = 22;
+
+pub fn build() -> Text<State> {
+    let label_extract: Text<State> = Text::default();
+    label_extract.x(X).y(Y).text("Used:");
+
+    label_extract
+}
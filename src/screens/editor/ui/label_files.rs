@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 59;
+static Y: isize = 16;
+
+pub fn build() -> Text<State> {
+    let label_files: Text<State> = Text::default();
+    label_files.x(X).y(Y).text("Files:");
+
+    label_files
+}
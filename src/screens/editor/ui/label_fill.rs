@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 59;
+static Y: isize = 21;
+
+pub fn build() -> Text<State> {
+    let label_fill: Text<State> = Text::default();
+    label_fill.x(X).y(Y).text("Fill:");
+
+    label_fill
+}
@@ -0,0 +1,17 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 11;
+static Y: isize = 23;
+
+pub fn build() -> Text<State> {
+    let label_frame_value: Text<State> = Text::default();
+    label_frame_value.x(X).y(Y).on_state(|el, state| {
+        let editor = &state.editor;
+        let value = format!("{}/{}", editor.active_frame + 1, editor.frames.len());
+        el.text(&format!("{:^5}", value));
+    });
+
+    label_frame_value
+}
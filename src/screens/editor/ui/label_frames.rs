@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 1;
+static Y: isize = 23;
+
+pub fn build() -> Text<State> {
+    let label_frames: Text<State> = Text::default();
+    label_frames.x(X).y(Y).text("Frames:");
+
+    label_frames
+}
@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 59;
+static Y: isize = 20;
+
+pub fn build() -> Text<State> {
+    let label_palette: Text<State> = Text::default();
+    label_palette.x(X).y(Y).text("Pal.:");
+
+    label_palette
+}
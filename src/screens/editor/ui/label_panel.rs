@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 59;
+static Y: isize = 15;
+
+pub fn build() -> Text<State> {
+    let label_panel: Text<State> = Text::default();
+    label_panel.x(X).y(Y).text("Panel:");
+
+    label_panel
+}
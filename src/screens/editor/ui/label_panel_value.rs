@@ -0,0 +1,15 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 68;
+static Y: isize = 15;
+
+pub fn build() -> Text<State> {
+    let label_panel_value: Text<State> = Text::default();
+    label_panel_value.x(X).y(Y).on_state(|el, state| {
+        el.text(&format!("{:^9}", state.editor.panel.label()));
+    });
+
+    label_panel_value
+}
@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 59;
+static Y: isize = 7;
+
+pub fn build() -> Text<State> {
+    let label_session: Text<State> = Text::default();
+    label_session.x(X).y(Y).text("Sess.:");
+
+    label_session
+}
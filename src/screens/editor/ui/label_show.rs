@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 59;
+static Y: isize = 17;
+
+pub fn build() -> Text<State> {
+    let label_show: Text<State> = Text::default();
+    label_show.x(X).y(Y).text("Show:");
+
+    label_show
+}
@@ -6,8 +6,8 @@ static X: isize = 59;
 static Y: isize = 2;
 
 pub fn build() -> Text<State> {
-    let label_new: Text<State> = Text::default();
-    label_new.x(X).y(Y).text("New:");
+    let label_size: Text<State> = Text::default();
+    label_size.x(X).y(Y).text("Size:");
 
-    label_new
+    label_size
 }
@@ -0,0 +1,16 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 68;
+static Y: isize = 2;
+
+pub fn build() -> Text<State> {
+    let label_size_value: Text<State> = Text::default();
+    label_size_value.x(X).y(Y).on_state(|el, state| {
+        let size = state.editor.size;
+        el.text(&format!("{:^5}", format!("{}x{}", size, size)));
+    });
+
+    label_size_value
+}
@@ -0,0 +1,15 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 10;
+static Y: isize = 21;
+
+pub fn build() -> Text<State> {
+    let label_slot_count: Text<State> = Text::default();
+    label_slot_count.x(X).y(Y).on_state(|el, state| {
+        el.text(&format!("{:>2}", state.editor.palette_colors.len()));
+    });
+
+    label_slot_count
+}
@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 1;
+static Y: isize = 21;
+
+pub fn build() -> Text<State> {
+    let label_slots: Text<State> = Text::default();
+    label_slots.x(X).y(Y).text("Slots:");
+
+    label_slots
+}
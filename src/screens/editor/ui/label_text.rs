@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 59;
+static Y: isize = 23;
+
+pub fn build() -> Text<State> {
+    let label_text: Text<State> = Text::default();
+    label_text.x(X).y(Y).text("Text:");
+
+    label_text
+}
@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 59;
+static Y: isize = 3;
+
+pub fn build() -> Text<State> {
+    let label_vision: Text<State> = Text::default();
+    label_vision.x(X).y(Y).text("Sim.:");
+
+    label_vision
+}
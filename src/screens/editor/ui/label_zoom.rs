@@ -0,0 +1,13 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+
+static X: isize = 59;
+static Y: isize = 6;
+
+pub fn build() -> Text<State> {
+    let label_zoom: Text<State> = Text::default();
+    label_zoom.x(X).y(Y).text("Zoom:");
+
+    label_zoom
+}
@@ -0,0 +1,101 @@
+use super::utils::*;
+use crate::core::config::config;
+use crate::core::model::{AppPhase, Panel, State};
+use crate::ui::pixels::color_decor;
+use incredible::*;
+
+/// Bottom row of the panel: add, delete, move up, move down, merge down.
+const ACTIONS: &str = " +  -  ▲  ▼  Merge";
+/// Text color of the active layer, on the theme's highlight.
+const ACTIVE_TEXT: u8 = 16;
+
+fn layers_look(state: &State) -> Look {
+    let mut rows = vec![vec![Block::new(' ', Decor::default()); PANEL_COLS]; PANEL_ROWS];
+    let editor = &state.editor;
+    let active = color_decor(Some(ACTIVE_TEXT), Some(config().highlight));
+
+    put_text(&mut rows[0], 0, "Layers", Decor::default());
+    put_text(&mut rows[0], 9, "Save:", Decor::default());
+    let save = if editor.export_layers {
+        "layer"
+    } else {
+        "flat"
+    };
+    put_text(&mut rows[0], 14, save, active.clone());
+
+    // Top layer first, like the stacking on the canvas.
+    for (i, layer) in editor.layers.iter().enumerate().rev() {
+        let row = &mut rows[editor.layers.len() - i];
+        let decor = if i == editor.active_layer {
+            active.clone()
+        } else {
+            Decor::default()
+        };
+        *row = vec![Block::new(' ', decor.clone()); PANEL_COLS];
+        let eye = if layer.visible { "●" } else { "○" };
+        put_text(row, 0, eye, decor.clone());
+        put_text(row, 2, &layer.name, decor);
+    }
+
+    put_text(&mut rows[PANEL_ROWS - 1], 0, ACTIONS, Decor::default());
+
+    Look::from(rows)
+}
+
+pub fn build() -> Element<State> {
+    let layers: Element<State> = Element::new();
+    layers
+        .x(PANEL_X)
+        .y(PANEL_Y)
+        .look(Look::from((PANEL_COLS, PANEL_ROWS, ' ')))
+        .on_mouse(|el, state, event| {
+            if event.mouse != Mouse::Click || state.editor.panel != Panel::Layers {
+                return;
+            }
+            let col = event.x.saturating_sub(el.visual.x.get()) as usize;
+            let row = event.y.saturating_sub(el.visual.y.get()) as usize;
+            let editor = &mut state.editor;
+            let count = editor.layers.len();
+
+            if row == 0 {
+                if col >= 14 {
+                    editor.export_layers = !editor.export_layers;
+                }
+            } else if row == PANEL_ROWS - 1 {
+                match col {
+                    0..=2 => editor.add_layer(),
+                    3..=5 => editor.delete_layer(),
+                    6..=8 => editor.move_layer(true),
+                    9..=11 => editor.move_layer(false),
+                    13..=17 => editor.merge_layer_down(),
+                    _ => {}
+                }
+            } else if row <= count {
+                let index = count - row;
+                if col <= 1 {
+                    editor.toggle_layer(index);
+                } else {
+                    editor.active_layer = index;
+                }
+            }
+        })
+        .on_state(|el, state| {
+            let active = state.flow.phase == AppPhase::Main && state.editor.panel == Panel::Layers;
+            if active {
+                el.showed(true);
+                el.look(layers_look(state));
+                el.draw();
+                return;
+            }
+
+            // On hide, draw a blank patch once at the same location to clear stale pixels.
+            if el.get_showed() {
+                el.showed(true);
+                el.look(Look::from((PANEL_COLS, PANEL_ROWS, ' ')));
+                el.draw();
+            }
+            el.showed(false);
+        });
+
+    layers
+}
@@ -1,18 +1,82 @@
-pub(crate) mod button_16;
-pub(crate) mod button_8;
+pub(crate) mod button_clear;
 pub(crate) mod button_exit;
+pub(crate) mod button_extract_frequency;
+pub(crate) mod button_extract_hue;
+pub(crate) mod button_fill_eight;
+pub(crate) mod button_fill_global;
+pub(crate) mod button_fill_gradient;
+pub(crate) mod button_fill_tolerance;
+pub(crate) mod button_frame_add;
+pub(crate) mod button_frame_delay;
+pub(crate) mod button_frame_delete;
+pub(crate) mod button_frame_duplicate;
+pub(crate) mod button_frame_next;
+pub(crate) mod button_frame_prev;
+pub(crate) mod button_gallery;
+pub(crate) mod button_onion_skin;
+pub(crate) mod button_palette_format;
+pub(crate) mod button_palette_link;
+pub(crate) mod button_palette_page;
+pub(crate) mod button_palette_purge;
+pub(crate) mod button_palette_save;
+pub(crate) mod button_pan_down;
+pub(crate) mod button_pan_left;
+pub(crate) mod button_pan_right;
+pub(crate) mod button_pan_up;
+pub(crate) mod button_panel_next;
+pub(crate) mod button_panel_prev;
+pub(crate) mod button_play;
 pub(crate) mod button_save;
-pub(crate) mod canvas_16;
-pub(crate) mod canvas_8;
+pub(crate) mod button_session_share;
+pub(crate) mod button_settings;
+pub(crate) mod button_show_checker;
+pub(crate) mod button_show_crosshair;
+pub(crate) mod button_show_grid;
+pub(crate) mod button_show_guides;
+pub(crate) mod button_size_next;
+pub(crate) mod button_size_prev;
+pub(crate) mod button_slot_add;
+pub(crate) mod button_slot_remove;
+pub(crate) mod button_snap;
+pub(crate) mod button_snap_dither;
+pub(crate) mod button_text_font;
+pub(crate) mod button_text_stamp;
+pub(crate) mod button_vision;
+pub(crate) mod button_zoom_fit;
+pub(crate) mod button_zoom_in;
+pub(crate) mod button_zoom_out;
+pub(crate) mod canvas;
 pub(crate) mod color_candidate;
 pub(crate) mod color_picker_16;
 pub(crate) mod color_picker_216;
 pub(crate) mod color_picker_empty;
 pub(crate) mod color_picker_gray;
 pub(crate) mod color_picker_palette;
+pub(crate) mod color_secondary;
 pub(crate) mod color_selected;
+pub(crate) mod export;
 pub(crate) mod label_color_candidate;
 pub(crate) mod label_color_selected;
+pub(crate) mod label_edit;
 pub(crate) mod label_end;
-pub(crate) mod label_new;
+pub(crate) mod label_extract;
+pub(crate) mod label_files;
+pub(crate) mod label_fill;
+pub(crate) mod label_frame_value;
+pub(crate) mod label_frames;
+pub(crate) mod label_palette;
+pub(crate) mod label_panel;
+pub(crate) mod label_panel_value;
+pub(crate) mod label_session;
+pub(crate) mod label_show;
+pub(crate) mod label_size;
+pub(crate) mod label_size_value;
+pub(crate) mod label_slot_count;
+pub(crate) mod label_slots;
+pub(crate) mod label_text;
+pub(crate) mod label_vision;
+pub(crate) mod label_zoom;
+pub(crate) mod layers;
+pub(crate) mod preview;
+pub(crate) mod usage;
 pub(crate) mod utils;
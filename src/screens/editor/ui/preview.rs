@@ -0,0 +1,174 @@
+use super::utils::*;
+use crate::core::{
+    color::{ansi8_to_rgb, color_usage, contrast_ratio, simulate_vision},
+    model::{AppPhase, Panel, State, Vision, composite},
+};
+use crate::ui::pixels::{color_decor, half_block_rows, sample_grid};
+use incredible::*;
+use std::path::Path;
+
+/// Largest icon shown at 1:1; bigger sizes are sampled down to it.
+const ACTUAL_MAX: usize = 16;
+/// Icon size inside the mock browser tabs.
+const TAB_ICON: usize = 8;
+/// Mock tab themes: (background, text color).
+const LIGHT_TAB: (u8, u8) = (255, 236);
+const DARK_TAB: (u8, u8) = (236, 252);
+/// Typical light and dark browser tab backgrounds for the contrast check.
+const LIGHT_BACKGROUND: [u8; 3] = [0xff, 0xff, 0xff];
+const DARK_BACKGROUND: [u8; 3] = [0x20, 0x21, 0x24];
+/// Contrast below this (WCAG, for graphics) is flagged.
+const MIN_CONTRAST: f32 = 3.0;
+/// Share of the painted pixels (in percent) that makes a color dominant.
+const DOMINANT_PERCENT: usize = 10;
+
+/// `data` as seen with `vision`.
+fn simulated(data: &[Option<u8>], vision: Vision) -> Vec<Option<u8>> {
+    if vision == Vision::Normal {
+        return data.to_vec();
+    }
+    let mut seen: [Option<u8>; 256] = [None; 256];
+    data.iter()
+        .map(|cell| {
+            cell.map(|code| {
+                *seen[code as usize].get_or_insert_with(|| simulate_vision(code, vision))
+            })
+        })
+        .collect()
+}
+
+/// Lowest contrast of the dominant colors against `background`.
+fn dominant_contrast(data: &[Option<u8>], background: [u8; 3]) -> Option<f32> {
+    let usage = color_usage(&[data]);
+    let painted: usize = usage.iter().map(|&(_, count)| count).sum();
+    usage
+        .iter()
+        .enumerate()
+        .filter(|&(i, &(_, count))| i == 0 || count * 100 >= painted * DOMINANT_PERCENT)
+        .map(|(_, &(code, _))| contrast_ratio(ansi8_to_rgb(code), background))
+        .min_by(f32::total_cmp)
+}
+
+/// "Light 4.5 Dark 1.2" with poor contrasts in red and flagged with `!`.
+fn contrast_row(data: &[Option<u8>]) -> Vec<Block> {
+    let mut row = vec![Block::new(' ', Decor::default()); PANEL_COLS];
+    let mut col = 0;
+    for (name, background) in [("Light", LIGHT_BACKGROUND), ("Dark", DARK_BACKGROUND)] {
+        let Some(ratio) = dominant_contrast(data, background) else {
+            continue;
+        };
+        let poor = ratio < MIN_CONTRAST;
+        let text = format!("{} {:.1}{}", name, ratio, if poor { "!" } else { "" });
+        let decor = if poor {
+            color_decor(Some(196), None)
+        } else {
+            Decor::default()
+        };
+        put_text(&mut row, col, &text, decor);
+        col += text.chars().count() + 1;
+    }
+    row
+}
+
+/// Mock browser tab, four rows high, with the icon next to a page title.
+fn tab_rows(icon: &[Option<u8>], title: &str, theme: (u8, u8)) -> Vec<Vec<Block>> {
+    let (background, text) = theme;
+    let fill = color_decor(Some(text), Some(background));
+
+    let mut rows = vec![vec![Block::new(' ', fill.clone()); PANEL_COLS]; TAB_ICON / 2];
+    for (row, icon_row) in rows
+        .iter_mut()
+        .zip(half_block_rows(icon, TAB_ICON, Some(background)))
+    {
+        for (col, block) in icon_row.into_iter().enumerate() {
+            row[1 + col] = block;
+        }
+    }
+    put_text(&mut rows[1], TAB_ICON + 2, title, fill.clone());
+    put_text(&mut rows[1], PANEL_COLS - 2, "×", fill);
+
+    rows
+}
+
+fn preview_look(state: &State) -> Look {
+    let mut rows = vec![vec![Block::new(' ', Decor::default()); PANEL_COLS]; PANEL_ROWS];
+    let size = state.editor.size as usize;
+    let vision = state.editor.vision;
+    let data = &simulated(&state.editor.composite(), vision);
+
+    // Actual size: one pixel per half cell, labelled as scaled when the icon
+    // is too big for the panel.
+    let actual_size = size.min(ACTUAL_MAX);
+    let actual = sample_grid(data, size, actual_size);
+    let label = if size > ACTUAL_MAX {
+        format!("Scaled to {}x{}", actual_size, actual_size)
+    } else {
+        "Actual size".to_string()
+    };
+    put_text(&mut rows[0], 0, &label, Decor::default());
+    let left = (PANEL_COLS - actual_size) / 2;
+    for (i, icon_row) in half_block_rows(&actual, actual_size, None)
+        .into_iter()
+        .enumerate()
+    {
+        for (col, block) in icon_row.into_iter().enumerate() {
+            rows[1 + i][left + col] = block;
+        }
+    }
+
+    // Tabs use the hand-tuned 8x8 variant when there is one.
+    let small = composite(&state.editor.layers, TAB_ICON as u8);
+    let tab_icon = if small.iter().any(|cell| cell.is_some()) {
+        simulated(&small, vision)
+    } else {
+        sample_grid(data, size, TAB_ICON)
+    };
+    let title = Path::new(&state.editor.file_path)
+        .file_stem()
+        .map(|stem| {
+            stem.to_string_lossy()
+                .chars()
+                .take(PANEL_COLS - TAB_ICON - 5)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let tabs_top = PANEL_ROWS - TAB_ICON;
+    rows[tabs_top - 1] = contrast_row(data);
+    for (i, row) in tab_rows(&tab_icon, &title, LIGHT_TAB)
+        .into_iter()
+        .chain(tab_rows(&tab_icon, &title, DARK_TAB))
+        .enumerate()
+    {
+        rows[tabs_top + i] = row;
+    }
+
+    Look::from(rows)
+}
+
+pub fn build() -> Element<State> {
+    let preview: Element<State> = Element::new();
+    preview
+        .x(PANEL_X)
+        .y(PANEL_Y)
+        .look(Look::from((PANEL_COLS, PANEL_ROWS, ' ')))
+        .on_state(|el, state| {
+            let active = state.flow.phase == AppPhase::Main && state.editor.panel == Panel::Preview;
+            if active {
+                el.showed(true);
+                el.look(preview_look(state));
+                el.draw();
+                return;
+            }
+
+            // On hide, draw a blank patch once at the same location to clear stale pixels.
+            if el.get_showed() {
+                el.showed(true);
+                el.look(Look::from((PANEL_COLS, PANEL_ROWS, ' ')));
+                el.draw();
+            }
+            el.showed(false);
+        });
+
+    preview
+}
@@ -0,0 +1,112 @@
+use super::utils::*;
+use crate::core::{
+    color::{ansi8_to_rgb, color_usage},
+    model::{AppPhase, Panel, State},
+};
+use crate::ui::pixels::color_decor;
+use incredible::*;
+
+/// Most unused palette colors listed below the used ones.
+const UNUSED_MAX: usize = 6;
+/// Color for palette entries that are no longer used.
+const UNUSED_COLOR: u8 = 196;
+
+/// Swatch and hex value of `code`, starting at column 0.
+fn color_row(code: u8) -> Vec<Block> {
+    let mut row = vec![Block::new(' ', Decor::default()); PANEL_COLS];
+    row[0] = Block::new(' ', color_decor(None, Some(code)));
+    row[1] = Block::new(' ', color_decor(None, Some(code)));
+    let [r, g, b] = ansi8_to_rgb(code);
+    put_text(
+        &mut row,
+        3,
+        &format!("#{:02x}{:02x}{:02x}", r, g, b),
+        Decor::default(),
+    );
+    row
+}
+
+fn usage_look(state: &State) -> Look {
+    let mut rows = vec![vec![Block::new(' ', Decor::default()); PANEL_COLS]; PANEL_ROWS];
+    let data = &state.editor.composite();
+    let used = color_usage(&[data]);
+    let unused = state.editor.unused_palette_colors();
+
+    let header = format!(
+        "{:<10}{:>5}{:>5}",
+        format!("{} used", used.len()),
+        "px",
+        "%"
+    );
+    put_text(&mut rows[0], 0, &header, Decor::default());
+
+    // Unused palette entries go at the bottom, under their own heading.
+    let unused_rows = if unused.is_empty() {
+        0
+    } else {
+        unused.len().min(UNUSED_MAX) + 1
+    };
+    let used_rows = PANEL_ROWS - 1 - unused_rows;
+
+    let cells = data.len().max(1);
+    for (i, &(code, count)) in used.iter().enumerate().take(used_rows) {
+        let row = &mut rows[1 + i];
+        if i + 1 == used_rows && used.len() > used_rows {
+            put_text(
+                row,
+                0,
+                &format!("+{} more", used.len() - i),
+                Decor::default(),
+            );
+            break;
+        }
+        *row = color_row(code);
+        let percent = count * 100 / cells;
+        put_text(
+            row,
+            10,
+            &format!("{:>5}{:>4}%", count, percent),
+            Decor::default(),
+        );
+    }
+
+    if unused_rows > 0 {
+        let top = PANEL_ROWS - unused_rows;
+        let warn = color_decor(Some(UNUSED_COLOR), None);
+        put_text(&mut rows[top], 0, "Unused in palette", warn.clone());
+        for (i, &code) in unused.iter().take(UNUSED_MAX).enumerate() {
+            let row = &mut rows[top + 1 + i];
+            *row = color_row(code);
+            put_text(row, 14, "unused", warn.clone());
+        }
+    }
+
+    Look::from(rows)
+}
+
+pub fn build() -> Element<State> {
+    let usage: Element<State> = Element::new();
+    usage
+        .x(PANEL_X)
+        .y(PANEL_Y)
+        .look(Look::from((PANEL_COLS, PANEL_ROWS, ' ')))
+        .on_state(|el, state| {
+            let active = state.flow.phase == AppPhase::Main && state.editor.panel == Panel::Usage;
+            if active {
+                el.showed(true);
+                el.look(usage_look(state));
+                el.draw();
+                return;
+            }
+
+            // On hide, draw a blank patch once at the same location to clear stale pixels.
+            if el.get_showed() {
+                el.showed(true);
+                el.look(Look::from((PANEL_COLS, PANEL_ROWS, ' ')));
+                el.draw();
+            }
+            el.showed(false);
+        });
+
+    usage
+}
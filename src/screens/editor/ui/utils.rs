@@ -1,4 +1,20 @@
-use crate::core::model::State;
+use crate::core::{
+    color::contrast_color,
+    config::config,
+    model::{
+        AppPhase, CANVAS_SIZES, EditorState, FillOptions, Focus, MAX_ZOOM, Panel, State, composite,
+    },
+};
+use crate::features::{
+    animation::animation_files,
+    export::icon_set_files,
+    fill::fill,
+    palette_file::{palette_file_name, parse_palette},
+    snap::snap_to_palette,
+    text_stamp::stamp_text,
+};
+use crate::platform;
+use crate::ui::pixels::{CHECKER, color_decor, void_block};
 use incredible::*;
 
 // Palette helpers
@@ -9,109 +25,595 @@ pub(crate) fn ideal_palette_index(curr: usize, palette: &Vec<Option<u8>>) -> usi
     }
 }
 
+/// Starts a new icon the way New Icon File on the launch screen does.
+pub(crate) fn start_new_in_state(state: &mut State) {
+    state.flow.phase = AppPhase::Launch;
+    state.flow.launch_template = 0;
+    state.flow.launch_start_new = true;
+    state.editor.file_handle = None;
+    state.editor.file_path = config().file_name.clone();
+}
+
 pub(crate) fn set_palette_in_state(state: &mut State, value: Option<u8>) {
     let curr = state.editor.palette_index;
 
     // Update the palette slot at `curr`
     if curr < state.editor.palette_colors.len() {
+        let old = state.editor.palette_colors[curr];
         state.editor.palette_colors[curr] = value;
+
+        // Linked: pixels follow their slot, and the slot stays selected.
+        if let (true, Some(from), Some(to)) = (state.editor.linked_palette, old, value) {
+            state.editor.recolor(from, to);
+            return;
+        }
     }
 
     // Move to the next ideal index
     state.editor.palette_index = ideal_palette_index(curr, &state.editor.palette_colors);
 }
 
-/// Canvas helpers
-pub(crate) fn canvas_look_from_data(size: usize, data: &[Option<u8>]) -> Look {
-    let rows: Vec<Vec<Block>> = (0..size)
-        .map(|row| {
-            (0..(size * 2))
-                .map(|col| {
-                    let half_col = col / 2;
-                    match data[row * size + half_col] {
-                        Some(ansi_code) => {
-                            let decor = Decor::new(
-                                Some(false),
-                                Some(false),
-                                None,
-                                None,
-                                Some(false),
-                                Some(false),
-                                Some(false),
-                                None,
-                                Some(Color::Ansi(ansi_code)),
-                            );
-                            Block::new(' ', decor)
-                        }
-                        None => Block::new(':', Decor::default()),
-                    }
-                })
-                .collect()
-        })
-        .collect();
-    Look::from(rows)
+/// Takes `color` from the canvas as the paintbrush and files it in the
+/// palette. With a linked palette a color already in it just selects its
+/// slot, and the canvas is never recolored by picking.
+pub(crate) fn pick_color_in_state(state: &mut State, color: Option<u8>) {
+    state.editor.paintbrush = color;
+    if state.editor.linked_palette {
+        let palette = &state.editor.palette_colors;
+        match palette.iter().position(|&c| c.is_some() && c == color) {
+            Some(idx) => state.editor.palette_index = idx,
+            None if palette.contains(&None) => {
+                state.editor.palette_index = ideal_palette_index(0, palette);
+                set_palette_in_state(state, color);
+            }
+            None => {}
+        }
+        return;
+    }
+    set_palette_in_state(state, color);
 }
 
-/// Simple 4-way iterative flood fill
-pub(crate) fn flood_fill(
-    data: &mut Vec<Option<u8>>,
-    size: usize,
-    row: usize,
-    col: usize,
-    target: Option<u8>,
-    replacement: Option<u8>,
+/// Drags the palette slot being moved over slot `target` (clamped to the
+/// last slot): a plain drag moves
+/// it there, shifting the slots between, and `swap` exchanges it with the
+/// slot under the pointer instead.
+pub(crate) fn drag_palette_slot_in_state(state: &mut State, target: usize, swap: bool) {
+    let editor = &mut state.editor;
+    let Some((origin, current)) = editor.palette_drag else {
+        return;
+    };
+    let target = target.min(editor.palette_colors.len().saturating_sub(1));
+    if target == current {
+        return;
+    }
+
+    let palette = &mut editor.palette_colors;
+    if swap {
+        palette.swap(origin, current);
+        palette.swap(origin, target);
+    } else {
+        let color = palette.remove(current);
+        palette.insert(target, color);
+    }
+    editor.palette_drag = Some((if swap { origin } else { target }, target));
+    editor.palette_index = target;
+}
+
+/// Picker grids as drawn on screen: (first code, columns, count).
+pub(crate) const CUBE: (usize, usize, usize) = (16, 12, 216);
+pub(crate) const GRAY: (usize, usize, usize) = (232, 2, 24);
+/// Rows of the 16-color column.
+pub(crate) const BASIC_COLORS: u8 = 16;
+
+/// Code of the terminal's own color drawn at `row` of the 16-color column.
+pub(crate) fn basic_color(row: u8) -> u8 {
+    Colors::rgb_to_ansi8(Platform::ansi_color_default(row))
+}
+
+/// Index of `current` inside a picker grid, or its first color.
+fn picker_index(current: Option<u8>, grid: (usize, usize, usize)) -> usize {
+    let (first, _, count) = grid;
+    current
+        .map(usize::from)
+        .filter(|&code| code >= first && code < first + count)
+        .map(|code| code - first)
+        .unwrap_or(0)
+}
+
+/// Moves `current` by (dx, dy) inside a picker grid, starting at its first
+/// color when `current` is not part of it.
+pub(crate) fn step_picker(
+    current: Option<u8>,
+    grid: (usize, usize, usize),
+    step: (isize, isize),
+) -> u8 {
+    let (first, columns, count) = grid;
+    let index = picker_index(current, grid);
+    let rows = count / columns;
+    let col = ((index % columns) as isize + step.0).clamp(0, columns as isize - 1) as usize;
+    let row = ((index / columns) as isize + step.1).clamp(0, rows as isize - 1) as usize;
+    (first + row * columns + col) as u8
+}
+
+/// Moves `current` by `delta` colors along a picker grid, row after row.
+pub(crate) fn step_picker_linear(
+    current: Option<u8>,
+    grid: (usize, usize, usize),
+    delta: isize,
+) -> u8 {
+    let (first, _, count) = grid;
+    let index = (picker_index(current, grid) as isize + delta).clamp(0, count as isize - 1);
+    (first + index as usize) as u8
+}
+
+/// Wheel direction: -1 scrolling up, 1 scrolling down.
+pub(crate) fn scroll_step(mouse: &Mouse) -> Option<isize> {
+    match mouse {
+        Mouse::ScrollUp => Some(-1),
+        Mouse::ScrollDown => Some(1),
+        _ => None,
+    }
+}
+
+/// Steps the candidate along a picker grid with the wheel, focusing that
+/// picker so `Space` / `Enter` picks the candidate.
+pub(crate) fn scroll_picker_in_state(
+    state: &mut State,
+    grid: (usize, usize, usize),
+    focus: Focus,
+    delta: isize,
 ) {
-    let mut stack = vec![(row, col)];
+    state.editor.candidate = Some(step_picker_linear(state.editor.candidate, grid, delta));
+    state.editor.picker_mode = true;
+    state.editor.focus = Some(focus);
+}
+
+/// Makes the neighbouring palette slot active and its color the paintbrush.
+pub(crate) fn select_palette_slot(state: &mut State, forward: bool) {
+    let count = state.editor.palette_colors.len();
+    if count == 0 {
+        return;
+    }
+    let current = state.editor.palette_index.min(count - 1);
+    let next = if forward {
+        (current + 1) % count
+    } else {
+        (current + count - 1) % count
+    };
+    state.editor.palette_index = next;
+    state.editor.paintbrush = state.editor.palette_colors[next];
+}
+
+/// Re-maps the active canvas onto the palette colors.
+pub(crate) fn snap_to_palette_in_state(state: &mut State) {
+    let palette: Vec<u8> = state
+        .editor
+        .palette_colors
+        .iter()
+        .flatten()
+        .copied()
+        .collect();
+    if palette.is_empty() {
+        state.flow.status = Some("Palette is empty".to_string());
+        return;
+    }
+    let size = state.editor.size as usize;
+    let dither = state.editor.snap_dither;
+    snap_to_palette(state.editor.canvas_data_mut(), size, &palette, dither);
+}
 
-    while let Some((r, c)) = stack.pop() {
-        if r >= size || c >= size {
-            continue;
+/// Replaces the palette with the colors of the palette file `name`, or
+/// reports why it could not be read.
+pub(crate) fn load_palette_in_state(state: &mut State, name: &str, bytes: Result<Vec<u8>, String>) {
+    let loaded = bytes.and_then(|bytes| parse_palette(name, &bytes));
+    state.flow.status = Some(match loaded {
+        Ok(colors) => {
+            state.editor.load_palette(&colors);
+            format!("Loaded {} colors", colors.len())
         }
+        Err(err_msg) => err_msg,
+    });
+}
+
+/// Loads the palette file Export writes next to the icon, in the chosen
+/// format.
+pub(crate) fn load_palette_file_in_state(state: &mut State) {
+    let file_name = palette_file_name(&state.editor.file_path, state.editor.palette_format);
+    let bytes = platform::get_io().read_file(&file_name);
+    load_palette_in_state(state, &file_name, bytes);
+}
+
+/// Saves `files` (path, bytes) next to the icon, or downloads them on the
+/// web, and reports it in the status.
+pub(crate) fn save_files_in_state(state: &mut State, files: Vec<(String, Vec<u8>)>) {
+    let io = platform::get_io();
+    let saved = files
+        .iter()
+        .try_for_each(|(path, bytes)| io.save_file(path, bytes));
+    let first = files.first().map_or("", |(path, _)| path.as_str());
+    state.flow.status = Some(match saved {
+        Ok(()) if files.len() > 1 => format!("Saved {} and {} more", first, files.len() - 1),
+        Ok(()) => format!("Saved {}", first),
+        Err(err_msg) => err_msg,
+    });
+}
+
+/// Saves the ICO and HTML snippet of the icon set, from the first frame.
+pub(crate) fn export_icon_set_in_state(state: &mut State) {
+    let frames = state.editor.document_frames(true);
+    match icon_set_files(&frames[0].variants(), &state.editor.file_path) {
+        Ok(files) => save_files_in_state(state, files),
+        Err(err_msg) => state.flow.status = Some(err_msg),
+    }
+}
+
+/// Saves the GIF, APNG and SMIL SVG of the animation.
+pub(crate) fn export_animation_in_state(state: &mut State) {
+    let frames = state.editor.document_frames(true);
+    let (size, px) = (state.editor.size, state.editor.export.px);
+    match animation_files(&frames, size, px, &state.editor.file_path) {
+        Ok(files) => save_files_in_state(state, files),
+        Err(err_msg) => state.flow.status = Some(err_msg),
+    }
+}
+
+/// Shows the text being typed for the stamp in the title bar.
+pub(crate) fn stamp_status_in_state(state: &mut State) {
+    let font = state.editor.stamp_font.label();
+    state.flow.status = state
+        .editor
+        .stamp
+        .as_ref()
+        .map(|text| format!("Text {}: {}_", font, text));
+}
 
-        let idx = r * size + c;
-        if data[idx] != target {
-            continue;
+/// Turns the text stamp on (with no text yet) or off.
+pub(crate) fn toggle_stamp_in_state(state: &mut State) {
+    state.editor.stamp = match state.editor.stamp {
+        Some(_) => None,
+        None => Some(String::new()),
+    };
+    stamp_status_in_state(state);
+}
+
+/// Stamps the typed text in the paint color with its top-left corner at
+/// `origin` (row, col), and turns the tool off.
+pub(crate) fn stamp_in_state(state: &mut State, origin: (usize, usize)) {
+    let Some(text) = state.editor.stamp.take() else {
+        return;
+    };
+    let size = state.editor.size as usize;
+    let (font, color) = (state.editor.stamp_font, state.editor.paintbrush);
+    stamp_text(
+        state.editor.canvas_data_mut(),
+        size,
+        origin,
+        &text,
+        font,
+        color,
+    );
+    stamp_status_in_state(state);
+}
+
+/// The flattened canvas as shown, with the text stamp previewed under the
+/// mouse (or the keyboard cursor) while the tool is on.
+pub(crate) fn canvas_display_data(editor: &EditorState) -> Vec<Option<u8>> {
+    let mut data = editor.composite();
+    let cursor = (editor.focus == Some(Focus::Canvas)).then_some(editor.cursor);
+    if let (Some(text), Some(origin)) = (&editor.stamp, editor.hover.or(cursor)) {
+        let size = editor.size as usize;
+        stamp_text(
+            &mut data,
+            size,
+            origin,
+            text,
+            editor.stamp_font,
+            editor.paintbrush,
+        );
+    }
+    data
+}
+
+/// Palette bar layout: slots per row, and slots per page (two rows).
+pub(crate) const PALETTE_COLUMNS: usize = 8;
+pub(crate) const PALETTE_PAGE: usize = 16;
+
+/// First slot of the palette page holding the selected slot.
+pub(crate) fn palette_page_start(state: &State) -> usize {
+    state.editor.palette_index / PALETTE_PAGE * PALETTE_PAGE
+}
+
+/// Palette slot under (col, row) of the palette bar, if the swatch exists.
+pub(crate) fn palette_slot_at(state: &State, col: usize, row: usize) -> Option<usize> {
+    let slot = palette_page_start(state) + row * PALETTE_COLUMNS + col / 4;
+    (col % 4 == 1 || col % 4 == 2)
+        .then_some(slot)
+        .filter(|&slot| col / 4 < PALETTE_COLUMNS && slot < state.editor.palette_colors.len())
+}
+
+/// Shows the next palette page (wrapping), selecting its first slot.
+pub(crate) fn step_palette_page_in_state(state: &mut State) {
+    let count = state.editor.palette_colors.len();
+    let next = palette_page_start(state) + PALETTE_PAGE;
+    let index = if next < count { next } else { 0 };
+    state.editor.palette_index = index;
+    state.editor.paintbrush = state.editor.palette_colors[index];
+}
+
+/// Canvas helpers
+///
+/// The canvas viewport is `VIEW_COLS` x `VIEW_ROWS` terminal cells at
+/// (`VIEW_X`, `VIEW_Y`) on an `APP_WIDTH` x `APP_HEIGHT` terminal, and grows
+/// into the spare cells of larger ones (see `spread`). At zoom level `z` each
+/// pixel is drawn as `2z` x `z` cells. Grids smaller than the viewport are
+/// centered in it, larger ones show the window starting at the pan offset.
+pub(crate) const VIEW_X: isize = 23;
+pub(crate) const VIEW_Y: isize = 3;
+pub(crate) const VIEW_COLS: usize = 32;
+pub(crate) const VIEW_ROWS: usize = 16;
+/// First row of the palette bar, slots and frames below the canvas.
+const BOTTOM_Y: isize = 21;
+
+/// Viewport size (columns, rows) with the spare cells on every side.
+pub(crate) fn view_dims(state: &State) -> (usize, usize) {
+    let (spare_cols, spare_rows) = state.flow.spare;
+    (VIEW_COLS + 2 * spare_cols, VIEW_ROWS + 2 * spare_rows)
+}
+
+/// Where an editor element laid out at (`x`, `y`) goes once the editor
+/// spreads into the spare cells: the tools right of the canvas move right,
+/// the pickers left of it and the rows under it move left and down, and the
+/// pan arrow above it moves up, leaving the room to the viewport.
+pub(crate) fn spread(state: &State, x: isize, y: isize) -> (isize, isize) {
+    let (spare_cols, spare_rows) = (state.flow.spare.0 as isize, state.flow.spare.1 as isize);
+    let right = x > VIEW_X + VIEW_COLS as isize;
+    let left = x < VIEW_X;
+    let dx = if right {
+        spare_cols
+    } else if left || y >= BOTTOM_Y {
+        -spare_cols
+    } else {
+        0
+    };
+    let dy = if right {
+        0
+    } else if y >= VIEW_Y + VIEW_ROWS as isize {
+        spare_rows
+    } else if !left && y < VIEW_Y {
+        -spare_rows
+    } else {
+        0
+    };
+    (x + dx, y + dy)
+}
+
+/// Largest zoom level at which the whole `size` grid fits a viewport of
+/// `view` cells.
+pub(crate) fn fit_zoom(size: usize, view: (usize, usize)) -> u8 {
+    (1..=MAX_ZOOM)
+        .rev()
+        .find(|&z| size * 2 * z as usize <= view.0 && size * z as usize <= view.1)
+        .unwrap_or(1)
+}
+
+/// How the active grid maps onto the viewport.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct CanvasView {
+    pub size: usize,
+    pub zoom: usize,
+    pub pan: (usize, usize),
+    /// Viewport size in cells (columns, rows).
+    pub view: (usize, usize),
+}
+
+impl CanvasView {
+    pub(crate) fn from_state(state: &State) -> Self {
+        let size = state.editor.size as usize;
+        let view = view_dims(state);
+        let zoom = state.editor.zoom.unwrap_or_else(|| fit_zoom(size, view));
+        CanvasView {
+            size,
+            zoom: zoom.clamp(1, MAX_ZOOM) as usize,
+            pan: (state.editor.pan_x, state.editor.pan_y),
+            view,
         }
+    }
+
+    /// Terminal cells used by one pixel (columns, rows).
+    pub(crate) fn cell(&self) -> (usize, usize) {
+        (self.zoom * 2, self.zoom)
+    }
+
+    /// Pixels visible in the viewport (columns, rows).
+    pub(crate) fn visible(&self) -> (usize, usize) {
+        let (cell_w, cell_h) = self.cell();
+        (
+            self.size.min(self.view.0 / cell_w),
+            self.size.min(self.view.1 / cell_h),
+        )
+    }
+
+    /// Largest useful pan offset (columns, rows).
+    pub(crate) fn max_pan(&self) -> (usize, usize) {
+        let (visible_cols, visible_rows) = self.visible();
+        (
+            self.size.saturating_sub(visible_cols),
+            self.size.saturating_sub(visible_rows),
+        )
+    }
+
+    /// Cell offset of the grid inside the viewport (centering small grids).
+    fn offset(&self) -> (usize, usize) {
+        let (cell_w, cell_h) = self.cell();
+        let (visible_cols, visible_rows) = self.visible();
+        (
+            (self.view.0 - visible_cols * cell_w) / 2,
+            (self.view.1 - visible_rows * cell_h) / 2,
+        )
+    }
+
+    /// Grid position (row, col) of a viewport cell and the cell's offset
+    /// (x, y) inside that pixel, if it shows a pixel.
+    pub(crate) fn locate(
+        &self,
+        view_col: usize,
+        view_row: usize,
+    ) -> Option<((usize, usize), (usize, usize))> {
+        let (off_x, off_y) = self.offset();
+        let (cell_w, cell_h) = self.cell();
+        let (visible_cols, visible_rows) = self.visible();
+        if view_col < off_x || view_row < off_y {
+            return None;
+        }
+
+        let (rel_x, rel_y) = (view_col - off_x, view_row - off_y);
+        let (col, row) = (rel_x / cell_w, rel_y / cell_h);
+        if col >= visible_cols || row >= visible_rows {
+            return None;
+        }
+
+        let (row, col) = (row + self.pan.1, col + self.pan.0);
+        (row < self.size && col < self.size)
+            .then_some(((row, col), (rel_x % cell_w, rel_y % cell_h)))
+    }
 
-        data[idx] = replacement;
+    /// Grid position (row, col) of a viewport cell, if it shows a pixel.
+    pub(crate) fn cell_at_view(&self, view_col: usize, view_row: usize) -> Option<(usize, usize)> {
+        self.locate(view_col, view_row).map(|(cell, _)| cell)
+    }
 
-        if r > 0 {
-            stack.push((r - 1, c));
-        } // Up
-        if r + 1 < size {
-            stack.push((r + 1, c));
-        } // Down
-        if c > 0 {
-            stack.push((r, c - 1));
-        } // Left
-        if c + 1 < size {
-            stack.push((r, c + 1));
-        } // Right
+    /// Grid position (row, col) under the mouse, if it is over a visible pixel.
+    pub(crate) fn cell_at(
+        &self,
+        el: &Element<State>,
+        mouse_x: isize,
+        mouse_y: isize,
+    ) -> Option<(usize, usize)> {
+        let rel_x = mouse_x - el.visual.x.get();
+        let rel_y = mouse_y - el.visual.y.get();
+        if rel_x < 0 || rel_y < 0 {
+            return None;
+        }
+        self.cell_at_view(rel_x as usize, rel_y as usize)
     }
 }
 
-pub(crate) fn canvas_data_from_click(
-    el: &Element<State>,
+/// Keeps the pan offset within the grid for the current size and zoom.
+pub(crate) fn clamp_pan_in_state(state: &mut State) {
+    let (max_x, max_y) = CanvasView::from_state(state).max_pan();
+    state.editor.pan_x = state.editor.pan_x.min(max_x);
+    state.editor.pan_y = state.editor.pan_y.min(max_y);
+}
+
+pub(crate) fn canvas_look_from_data(
+    view: &CanvasView,
+    data: &[Option<u8>],
+    editor: &EditorState,
+) -> Look {
+    let (cell_w, cell_h) = view.cell();
+    let center = view.size / 2;
+    let options = &editor.view;
+    let hover = editor.hover;
+    let cursor = (editor.focus == Some(Focus::Canvas)).then_some(editor.cursor);
+    // Line colors for the grid overlay and the center guides.
+    let config = config();
+    let (grid_color, guide_color) = (config.grid, config.guide);
+    // Onion skin: the previous frame shows faintly through void cells while
+    // the animation is stopped.
+    let onion =
+        (editor.onion_skin && editor.frames.len() > 1 && editor.playing.is_none()).then(|| {
+            composite(
+                editor.frame_layers(editor.previous_frame()),
+                view.size as u8,
+            )
+        });
+
+    let rows: Vec<Vec<Block>> = (0..view.view.1)
+        .map(|view_row| {
+            (0..view.view.0)
+                .map(|view_col| {
+                    let Some(((row, col), (sub_x, sub_y))) = view.locate(view_col, view_row) else {
+                        return Block::new(' ', Decor::default());
+                    };
+
+                    let cell = data.get(row * view.size + col).copied().flatten();
+                    let background =
+                        cell.or_else(|| options.checkerboard.then(|| CHECKER[(row + col) % 2]));
+
+                    // Keyboard cursor brackets the pixel on its middle row.
+                    if cursor == Some((row, col)) && sub_y == cell_h / 2 {
+                        let ch = match sub_x {
+                            0 => Some('['),
+                            x if x == cell_w - 1 => Some(']'),
+                            _ => None,
+                        };
+                        if let Some(ch) = ch {
+                            let color = background.map(contrast_color).unwrap_or(guide_color);
+                            return Block::new(ch, color_decor(Some(color), background));
+                        }
+                    }
+
+                    // Lines sit on the left edge of a pixel, and on its top edge
+                    // once the pixel is more than one cell high.
+                    let left_edge = sub_x == 0;
+                    let top_edge = sub_y == 0 && cell_h > 1;
+                    let on_guide = options.guides
+                        && ((left_edge && col == center) || (top_edge && row == center));
+                    let on_grid = options.grid && (left_edge || top_edge);
+                    if on_guide || on_grid {
+                        let line_color = if on_guide { guide_color } else { grid_color };
+                        let ch = if left_edge { '▏' } else { '▔' };
+                        return Block::new(ch, color_decor(Some(line_color), background));
+                    }
+
+                    // Crosshair marks sit in the last cell of a pixel, clear of the lines.
+                    let marker_cell = sub_x == cell_w - 1 && sub_y == cell_h - 1;
+                    let crossed = hover.filter(|&(r, c)| r == row || c == col);
+                    if options.crosshair && marker_cell && crossed.is_some() {
+                        let ch = if crossed == Some((row, col)) {
+                            '+'
+                        } else {
+                            '·'
+                        };
+                        let color = background.map(contrast_color).unwrap_or(guide_color);
+                        return Block::new(ch, color_decor(Some(color), background));
+                    }
+
+                    let ghost = onion
+                        .as_ref()
+                        .and_then(|data| data.get(row * view.size + col).copied().flatten());
+                    match (cell, ghost) {
+                        (Some(ansi_code), _) => Block::new(' ', color_decor(None, Some(ansi_code))),
+                        (None, Some(ghost)) => {
+                            Block::new('░', color_decor(Some(ghost), background))
+                        }
+                        (None, None) => void_block(options.checkerboard, row + col),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    Look::from(rows)
+}
+
+pub(crate) fn canvas_data_from_cell(
     size: usize,
     data: &mut Vec<Option<u8>>,
-    paintbrush: Option<u8>,
-    mouse_x: isize,
-    mouse_y: isize,
+    brush: (Option<u8>, Option<u8>),
+    row: usize,
+    col: usize,
     prev_color_on_canvas: &mut Option<u8>,
-    fill: bool,
+    fill: Option<&FillOptions>,
 ) {
-    let row = mouse_y.saturating_sub(el.visual.y.get()) as usize;
-    let col = mouse_x.saturating_sub(el.visual.x.get()) as usize / 2;
+    // `brush` is the paint color and the color a gradient fill blends to.
+    let paintbrush = brush.0;
     if row < size && col < size {
         let idx = row * size + col;
-        if fill {
+        if let Some(options) = fill {
             // Double-click: the second Down already painted this cell. Restore
             // the color from before the paint stroke, then flood-fill that region.
             data[idx] = *prev_color_on_canvas;
-            let target = data[idx];
-            if target != paintbrush {
-                flood_fill(data, size, row, col, target, paintbrush);
-            }
+            fill(data, size, (row, col), brush, options);
         } else {
             // Remember the cell color before paint so a following double-click
             // can undo the stroke and flood-fill. Skip when the cell is already
@@ -125,3 +627,135 @@ pub(crate) fn canvas_data_from_click(
         }
     }
 }
+
+/// Pan helpers
+#[derive(Clone, Copy)]
+pub(crate) enum PanDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Pixels moved per pan step.
+const PAN_STEP: usize = 4;
+
+/// Moves the canvas viewport one step in `direction`, clamped to the grid.
+pub(crate) fn pan_in_state(state: &mut State, direction: PanDirection) {
+    let editor = &mut state.editor;
+    match direction {
+        PanDirection::Up => editor.pan_y = editor.pan_y.saturating_sub(PAN_STEP),
+        PanDirection::Down => editor.pan_y += PAN_STEP,
+        PanDirection::Left => editor.pan_x = editor.pan_x.saturating_sub(PAN_STEP),
+        PanDirection::Right => editor.pan_x += PAN_STEP,
+    }
+    clamp_pan_in_state(state);
+}
+
+/// Pan arrow text, blank when the grid fits the viewport along that axis.
+pub(crate) fn pan_arrow(state: &State, direction: PanDirection) -> &'static str {
+    let (max_x, max_y) = CanvasView::from_state(state).max_pan();
+    match direction {
+        PanDirection::Up if max_y > 0 => "▲",
+        PanDirection::Down if max_y > 0 => "▼",
+        PanDirection::Left if max_x > 0 => "◀",
+        PanDirection::Right if max_x > 0 => "▶",
+        _ => " ",
+    }
+}
+
+/// Zoom helpers
+///
+/// Sets the zoom level (`None` fits the grid to the viewport), keeping the pan
+/// offset valid.
+pub(crate) fn set_zoom_in_state(state: &mut State, zoom: Option<u8>) {
+    state.editor.zoom = zoom.map(|z| z.clamp(1, MAX_ZOOM));
+    clamp_pan_in_state(state);
+}
+
+/// Zoom level one step in or out from the current one.
+pub(crate) fn stepped_zoom(state: &State, zoom_in: bool) -> u8 {
+    let current = CanvasView::from_state(state).zoom as u8;
+    if zoom_in {
+        (current + 1).min(MAX_ZOOM)
+    } else {
+        current.saturating_sub(1).max(1)
+    }
+}
+
+/// Size helpers
+///
+/// Switches to the neighbouring entry of `CANVAS_SIZES`, keeping every variant.
+pub(crate) fn step_size_in_state(state: &mut State, forward: bool) {
+    let current = CANVAS_SIZES
+        .iter()
+        .position(|&size| size == state.editor.size)
+        .unwrap_or(0);
+    let next = if forward {
+        (current + 1).min(CANVAS_SIZES.len() - 1)
+    } else {
+        current.saturating_sub(1)
+    };
+    state.editor.set_size(CANVAS_SIZES[next]);
+}
+
+/// Panel helpers
+///
+/// Side panels replace the color pickers in this area.
+pub(crate) const PANEL_X: isize = 1;
+pub(crate) const PANEL_Y: isize = 2;
+pub(crate) const PANEL_COLS: usize = 20;
+pub(crate) const PANEL_ROWS: usize = 18;
+
+/// Switches to the neighbouring entry of `Panel::ALL`, wrapping around.
+pub(crate) fn step_panel_in_state(state: &mut State, forward: bool) {
+    let count = Panel::ALL.len();
+    let current = Panel::ALL
+        .iter()
+        .position(|&panel| panel == state.editor.panel)
+        .unwrap_or(0);
+    let next = if forward {
+        (current + 1) % count
+    } else {
+        (current + count - 1) % count
+    };
+    state.editor.panel = Panel::ALL[next];
+}
+
+/// Writes `text` into a row of blocks starting at `col`, clipped to the row.
+pub(crate) fn put_text(row: &mut [Block], col: usize, text: &str, decor: Decor) {
+    for (cell, ch) in row.iter_mut().skip(col).zip(text.chars()) {
+        *cell = Block::new(ch, decor.clone());
+    }
+}
+
+/// Keyboard helpers
+///
+/// Pans just enough for the keyboard cursor to be in view.
+pub(crate) fn reveal_cursor_in_state(state: &mut State) {
+    let (visible_cols, visible_rows) = CanvasView::from_state(state).visible();
+    let (row, col) = state.editor.cursor;
+    let editor = &mut state.editor;
+    if col < editor.pan_x {
+        editor.pan_x = col;
+    } else if col >= editor.pan_x + visible_cols {
+        editor.pan_x = col + 1 - visible_cols;
+    }
+    if row < editor.pan_y {
+        editor.pan_y = row;
+    } else if row >= editor.pan_y + visible_rows {
+        editor.pan_y = row + 1 - visible_rows;
+    }
+}
+
+/// Block for a picker swatch, marked with `+` when it is the keyboard pick.
+pub(crate) fn swatch_block(ansi_code: u8, marked: bool) -> Block {
+    if marked {
+        Block::new(
+            '+',
+            color_decor(Some(contrast_color(ansi_code)), Some(ansi_code)),
+        )
+    } else {
+        Block::new(' ', color_decor(None, Some(ansi_code)))
+    }
+}
@@ -0,0 +1,22 @@
+use super::ui::utils::*;
+use crate::core::model::State;
+use incredible::*;
+
+/// Keyboard navigation in the gallery browser.
+///
+/// - arrows or `h` `j` `k` `l` — move the selection
+/// - `Space` / `Enter` — open the selected icon
+/// - `Esc` / `q` — go back
+pub(crate) fn handle_key(state: &mut State, key: &Key) {
+    match key {
+        Key::Left | Key::Char('h') => step_gallery_in_state(state, -1),
+        Key::Right | Key::Char('l') => step_gallery_in_state(state, 1),
+        Key::Up | Key::Char('k') => step_gallery_in_state(state, -(GALLERY_COLUMNS as isize)),
+        Key::Down | Key::Char('j') => step_gallery_in_state(state, GALLERY_COLUMNS as isize),
+        Key::Enter | Key::Char(' ') => {
+            state.flow.gallery_open = !state.flow.gallery.is_empty();
+        }
+        Key::Escape | Key::Char('q') => close_gallery_in_state(state),
+        _ => {}
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{
+    core::model::{AppPhase, State},
+    ui::{APP_HEIGHT, APP_WIDTH},
+};
+use incredible::*;
+use incredible_elements::Rectangle;
+
+pub fn build() -> Rectangle<State> {
+    let wrapper: Rectangle<State> = Rectangle::new();
+    wrapper.width(APP_WIDTH).height(APP_HEIGHT).fill(Some(' '));
+    wrapper.on_state(|el, state| {
+        el.showed(!state.flow.viewport_too_small && state.flow.phase == AppPhase::Gallery);
+    });
+
+    wrapper.add(super::ui::gallery_title::build());
+    wrapper.add(super::ui::gallery_grid::build());
+    wrapper.add(super::ui::button_gallery_open::build());
+    wrapper.add(super::ui::button_gallery_back::build());
+    wrapper.add(super::ui::button_gallery_page::build());
+    wrapper.add(super::ui::gallery_hint::build());
+
+    wrapper.showed(false);
+
+    wrapper
+}
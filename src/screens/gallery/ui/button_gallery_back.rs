@@ -0,0 +1,26 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 7;
+static Y: isize = 22;
+
+pub fn build() -> TextButton<State> {
+    let button_gallery_back: TextButton<State> = TextButton::default();
+    button_gallery_back
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Back")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                close_gallery_in_state(state);
+            }
+        });
+
+    button_gallery_back
+}
@@ -0,0 +1,25 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 1;
+static Y: isize = 22;
+
+pub fn build() -> TextButton<State> {
+    let button_gallery_open: TextButton<State> = TextButton::default();
+    button_gallery_open
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Open")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click && !state.flow.gallery.is_empty() {
+                state.flow.gallery_open = true;
+            }
+        });
+
+    button_gallery_open
+}
@@ -0,0 +1,32 @@
+use super::utils::*;
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static X: isize = 13;
+static Y: isize = 22;
+
+pub fn build() -> TextButton<State> {
+    let button_gallery_page: TextButton<State> = TextButton::default();
+    button_gallery_page
+        .x(X)
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("1/1")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                step_gallery_page_in_state(state);
+            }
+        })
+        .on_state(|el, state| {
+            // Current page of the grid out of all pages.
+            let pages = state.flow.gallery.len().div_ceil(GALLERY_PAGE).max(1);
+            let page = gallery_page_start(state) / GALLERY_PAGE + 1;
+            el.text(&format!("{}/{}", page, pages));
+        });
+
+    button_gallery_page
+}
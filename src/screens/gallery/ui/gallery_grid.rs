@@ -0,0 +1,106 @@
+use super::utils::*;
+use crate::core::config::config;
+use crate::core::model::{AppPhase, GalleryEntry, State};
+use crate::ui::pixels::{color_decor, half_block_rows, sample_grid};
+use incredible::*;
+use incredible_helpers_styling::*;
+
+static X: isize = 0;
+static Y: isize = 3;
+
+/// A tile: the thumbnail (two pixels per cell) over the file name, then a gap.
+const THUMB: usize = 16;
+const TILE_COLS: usize = 20;
+const TILE_ROWS: usize = THUMB / 2 + 2;
+const MARGIN: usize = (TILE_COLS - THUMB) / 2;
+const WIDTH: usize = GALLERY_COLUMNS * TILE_COLS;
+const HEIGHT: usize = (GALLERY_PAGE / GALLERY_COLUMNS) * TILE_ROWS - 1;
+/// Text color of the selected icon, on the theme's highlight.
+const SELECTED_TEXT: u8 = 16;
+
+/// The icon's drawing, resampled to the thumbnail size.
+fn thumbnail(entry: &GalleryEntry) -> Vec<Option<u8>> {
+    let payload = &entry.payload;
+    let data = payload
+        .frames
+        .first()
+        .map(|frame| frame.composite(payload.size))
+        .unwrap_or_default();
+    sample_grid(&data, payload.size as usize, THUMB)
+}
+
+fn grid_look(state: &State) -> Look {
+    let mut rows = vec![vec![Block::new(' ', Decor::default()); WIDTH]; HEIGHT];
+    if state.flow.gallery.is_empty() {
+        let note = "No Crumbicon or Rusticon SVGs here.";
+        for (i, ch) in note.chars().enumerate() {
+            rows[0][MARGIN + i] = Block::new(ch, Decor::default());
+        }
+        return Look::from(rows);
+    }
+
+    let start = gallery_page_start(state);
+    let highlight = config().highlight;
+    let entries = state.flow.gallery.iter().enumerate().skip(start);
+    for (index, entry) in entries.take(GALLERY_PAGE) {
+        let slot = index - start;
+        let (top, left) = (
+            slot / GALLERY_COLUMNS * TILE_ROWS,
+            slot % GALLERY_COLUMNS * TILE_COLS + MARGIN,
+        );
+        let selected = index == state.flow.gallery_index;
+        let backdrop = selected.then_some(highlight);
+        let thumb = half_block_rows(&thumbnail(entry), THUMB, backdrop);
+        for (i, blocks) in thumb.into_iter().enumerate() {
+            for (j, block) in blocks.into_iter().enumerate() {
+                rows[top + i][left + j] = block;
+            }
+        }
+
+        let decor = if selected {
+            color_decor(Some(SELECTED_TEXT), Some(highlight))
+        } else {
+            Decor::default()
+        };
+        let name: String = entry.name.chars().take(THUMB).collect();
+        for (j, ch) in format!("{:^1$}", name, THUMB).chars().enumerate() {
+            rows[top + THUMB / 2][left + j] = Block::new(ch, decor.clone());
+        }
+    }
+    Look::from(rows)
+}
+
+pub fn build() -> Element<State> {
+    let grid: Element<State> = Element::new();
+    grid.x(X)
+        .y(Y)
+        .pointer(Some(PointerShape::Pointer))
+        .look(Look::from((WIDTH, HEIGHT, ' ')))
+        .on_mouse(|el, state, event| {
+            let is_open = event.mouse == Mouse::DoubleClick;
+            if event.mouse != Mouse::Click && !is_open {
+                return;
+            }
+            let col = event.x.saturating_sub(el.visual.x.get()) as usize;
+            let row = event.y.saturating_sub(el.visual.y.get()) as usize;
+            let inside = (MARGIN..MARGIN + THUMB).contains(&(col % TILE_COLS))
+                && row % TILE_ROWS <= THUMB / 2;
+            let index = gallery_page_start(state)
+                + row / TILE_ROWS * GALLERY_COLUMNS
+                + (col / TILE_COLS).min(GALLERY_COLUMNS - 1);
+            if !inside || index >= state.flow.gallery.len() {
+                return;
+            }
+            state.flow.gallery_index = index;
+            state.flow.gallery_open = is_open;
+        })
+        .on_state(|el, state| {
+            if state.flow.viewport_too_small || state.flow.phase != AppPhase::Gallery {
+                return;
+            }
+            el.look(grid_look(state));
+            el.draw();
+        });
+
+    grid
+}
@@ -0,0 +1,18 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+use incredible_helpers_styling::*;
+
+static X: isize = 22;
+static Y: isize = 22;
+
+pub fn build() -> Text<State> {
+    let gallery_hint: Text<State> = Text::default();
+    gallery_hint
+        .x(X)
+        .y(Y)
+        .faint(Some(true))
+        .text("Arrows move, Enter opens, Esc goes back");
+
+    gallery_hint
+}
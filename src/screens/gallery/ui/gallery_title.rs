@@ -0,0 +1,25 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+use incredible_helpers_styling::*;
+
+static X: isize = 1;
+static Y: isize = 1;
+
+pub fn build() -> Text<State> {
+    let gallery_title: Text<State> = Text::default();
+    gallery_title
+        .x(X)
+        .y(Y)
+        .bold(Some(true))
+        .on_state(|el, state| {
+            let title = format!(
+                "Icons in {} ({})",
+                state.flow.gallery_dir,
+                state.flow.gallery.len()
+            );
+            el.text(&format!("{:<78}", title));
+        });
+
+    gallery_title
+}
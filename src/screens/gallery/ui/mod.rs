@@ -0,0 +1,7 @@
+pub(crate) mod button_gallery_back;
+pub(crate) mod button_gallery_open;
+pub(crate) mod button_gallery_page;
+pub(crate) mod gallery_grid;
+pub(crate) mod gallery_hint;
+pub(crate) mod gallery_title;
+pub(crate) mod utils;
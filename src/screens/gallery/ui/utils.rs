@@ -0,0 +1,47 @@
+use crate::core::model::{AppPhase, State};
+use crate::features::gallery::gallery_entries;
+use crate::platform;
+
+/// Gallery grid layout: tiles per row, and tiles per page.
+pub(crate) const GALLERY_COLUMNS: usize = 4;
+pub(crate) const GALLERY_PAGE: usize = GALLERY_COLUMNS * 2;
+
+/// Scans the gallery directory and shows the browser, which goes back to
+/// `return_to` when left.
+pub(crate) fn open_gallery_in_state(state: &mut State, return_to: AppPhase) {
+    let files = platform::get_io().gallery_files(&state.flow.gallery_dir);
+    state.flow.gallery = gallery_entries(files);
+    state.flow.gallery_index = 0;
+    state.flow.gallery_open = false;
+    state.flow.gallery_return = return_to;
+    state.flow.phase = AppPhase::Gallery;
+}
+
+/// Leaves the browser for the screen it was opened from.
+pub(crate) fn close_gallery_in_state(state: &mut State) {
+    state.flow.phase = state.flow.gallery_return.clone();
+}
+
+/// Moves the selection by `step` icons, stopping at either end.
+pub(crate) fn step_gallery_in_state(state: &mut State, step: isize) {
+    let count = state.flow.gallery.len() as isize;
+    let index = state.flow.gallery_index as isize + step;
+    if (0..count).contains(&index) {
+        state.flow.gallery_index = index as usize;
+    }
+}
+
+/// First icon of the page holding the selected one.
+pub(crate) fn gallery_page_start(state: &State) -> usize {
+    state.flow.gallery_index / GALLERY_PAGE * GALLERY_PAGE
+}
+
+/// Shows the next page (wrapping), selecting its first icon.
+pub(crate) fn step_gallery_page_in_state(state: &mut State) {
+    let next = gallery_page_start(state) + GALLERY_PAGE;
+    state.flow.gallery_index = if next < state.flow.gallery.len() {
+        next
+    } else {
+        0
+    };
+}
@@ -27,7 +27,12 @@ pub fn build() -> Rectangle<State> {
 
     wrapper.add(crate::ui::made_with::build());
     wrapper.add(super::ui::launch_hint::build());
+    wrapper.add(super::ui::recent_files::build());
+    wrapper.add(super::ui::template_picker::build());
     wrapper.add(super::ui::start_new::build());
+    wrapper.add(super::ui::template_name::build());
+    wrapper.add(super::ui::browse_icons::build());
+    wrapper.add(super::ui::open_settings::build());
     wrapper.elements_snap_center_x();
 
     wrapper.showed(false);
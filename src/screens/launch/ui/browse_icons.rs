@@ -0,0 +1,22 @@
+use crate::core::model::{AppPhase, State};
+use crate::screens::gallery::ui::utils::open_gallery_in_state;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+pub fn build() -> TextButton<State> {
+    let button: TextButton<State> = TextButton::default();
+    button
+        .y(22)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Browse Icons")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                open_gallery_in_state(state, AppPhase::Launch);
+            }
+        });
+
+    button
+}
@@ -1,2 +1,7 @@
+pub(crate) mod browse_icons;
 pub(crate) mod launch_hint;
+pub(crate) mod open_settings;
+pub(crate) mod recent_files;
 pub(crate) mod start_new;
+pub(crate) mod template_name;
+pub(crate) mod template_picker;
@@ -0,0 +1,70 @@
+use crate::core::model::{AppPhase, GalleryEntry, MAX_RECENT, State};
+use crate::ui::pixels::{half_block_rows, sample_grid};
+use incredible::*;
+
+/// Side of the thumbnails in pixels and the gap between them.
+const THUMB: usize = 8;
+const GAP: usize = 2;
+const WIDTH: usize = MAX_RECENT * (THUMB + GAP) - GAP;
+/// Thumbnails, then a row of names.
+const HEIGHT: usize = THUMB / 2 + 1;
+
+static Y: isize = 3;
+
+/// The icon's first frame, resampled to the thumbnail size.
+fn thumbnail(entry: &GalleryEntry) -> Vec<Option<u8>> {
+    let payload = &entry.payload;
+    let data = payload
+        .frames
+        .first()
+        .map(|frame| frame.composite(payload.size))
+        .unwrap_or_default();
+    sample_grid(&data, payload.size as usize, THUMB)
+}
+
+fn recent_look(state: &State) -> Look {
+    let mut rows = vec![vec![Block::new(' ', Decor::default()); WIDTH]; HEIGHT];
+
+    for (slot, entry) in state.flow.recent.iter().enumerate().take(MAX_RECENT) {
+        let left = slot * (THUMB + GAP);
+        for (row, blocks) in rows
+            .iter_mut()
+            .zip(half_block_rows(&thumbnail(entry), THUMB, None))
+        {
+            for (i, block) in blocks.into_iter().enumerate() {
+                row[left + i] = block;
+            }
+        }
+        for (i, c) in entry.name.chars().take(THUMB).enumerate() {
+            rows[HEIGHT - 1][left + i] = Block::new(c, Decor::default());
+        }
+    }
+    Look::from(rows)
+}
+
+pub fn build() -> Element<State> {
+    let recent: Element<State> = Element::new();
+    recent
+        .y(Y)
+        .pointer(Some(PointerShape::Pointer))
+        .look(Look::from((WIDTH, HEIGHT, ' ')))
+        .on_mouse(|el, state, event| {
+            if event.mouse != Mouse::Click {
+                return;
+            }
+            let col = event.x.saturating_sub(el.visual.x.get()) as usize;
+            let slot = col / (THUMB + GAP);
+            if col % (THUMB + GAP) < THUMB && slot < state.flow.recent.len() {
+                state.flow.recent_open = Some(slot);
+            }
+        })
+        .on_state(|el, state| {
+            if state.flow.viewport_too_small || state.flow.phase != AppPhase::Launch {
+                return;
+            }
+            el.look(recent_look(state));
+            el.draw();
+        });
+
+    recent
+}
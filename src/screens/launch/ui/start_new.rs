@@ -1,3 +1,4 @@
+use crate::core::config::config;
 use crate::core::model::State;
 use incredible::*;
 use incredible_elements::TextButton;
@@ -15,7 +16,7 @@ pub fn build() -> TextButton<State> {
             if event.mouse == Mouse::Click {
                 state.flow.launch_start_new = true;
                 state.editor.file_handle = None;
-                state.editor.file_path = "favicon.svg".to_string();
+                state.editor.file_path = config().file_name.clone();
             }
         });
 
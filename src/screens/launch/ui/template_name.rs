@@ -0,0 +1,17 @@
+use crate::core::model::State;
+use crate::features::templates::templates;
+use incredible::*;
+use incredible_elements::Text;
+
+pub fn build() -> Text<State> {
+    let template_name: Text<State> = Text::default();
+    template_name.y(21).on_state(|el, state| {
+        let name = templates()
+            .get(state.flow.launch_template)
+            .map(|template| format!("{} ({})", template.name, template.source.label()))
+            .unwrap_or_default();
+        el.text(&format!("{:^40}", name));
+    });
+
+    template_name
+}
@@ -0,0 +1,100 @@
+use crate::core::config::config;
+use crate::core::model::{AppPhase, State};
+use crate::features::templates::{Template, templates};
+use crate::ui::pixels::{half_block_rows, sample_grid};
+use incredible::*;
+use incredible_helpers_styling::*;
+
+/// Templates shown at once, and the side of their thumbnails in pixels.
+const PER_PAGE: usize = 6;
+const THUMB: usize = 8;
+const GAP: usize = 2;
+/// Page arrows, then the thumbnails.
+const WIDTH: usize = 2 + PER_PAGE * (THUMB + GAP) - GAP + 2;
+const HEIGHT: usize = THUMB / 2;
+/// Shows through the void pixels of the picked template.
+const PICKED_BACKDROP: u8 = 240;
+
+static Y: isize = 16;
+
+/// The template's drawing, resampled to the thumbnail size.
+fn thumbnail(template: &Template) -> Vec<Option<u8>> {
+    let payload = &template.payload;
+    let data = payload
+        .frames
+        .first()
+        .map(|frame| frame.composite(payload.size))
+        .unwrap_or_default();
+    sample_grid(&data, payload.size as usize, THUMB)
+}
+
+fn picker_look(state: &State) -> Look {
+    let mut rows = vec![vec![Block::new(' ', Decor::default()); WIDTH]; HEIGHT];
+    let all = templates();
+    let picked = state.flow.launch_template;
+    let start = picked / PER_PAGE * PER_PAGE;
+
+    for (slot, template) in all.iter().enumerate().skip(start).take(PER_PAGE) {
+        let left = 2 + (slot - start) * (THUMB + GAP);
+        let backdrop = (slot == picked).then_some(PICKED_BACKDROP);
+        for (row, blocks) in
+            rows.iter_mut()
+                .zip(half_block_rows(&thumbnail(template), THUMB, backdrop))
+        {
+            for (i, block) in blocks.into_iter().enumerate() {
+                row[left + i] = block;
+            }
+        }
+    }
+
+    if all.len() > PER_PAGE {
+        rows[HEIGHT / 2][0] = Block::new('◀', Decor::default());
+        rows[HEIGHT / 2][WIDTH - 1] = Block::new('▶', Decor::default());
+    }
+    Look::from(rows)
+}
+
+pub fn build() -> Element<State> {
+    let picker: Element<State> = Element::new();
+    picker
+        .y(Y)
+        .pointer(Some(PointerShape::Pointer))
+        .look(Look::from((WIDTH, HEIGHT, ' ')))
+        .on_mouse(|el, state, event| {
+            let is_open = event.mouse == Mouse::DoubleClick;
+            if event.mouse != Mouse::Click && !is_open {
+                return;
+            }
+            let count = templates().len();
+            let col = event.x.saturating_sub(el.visual.x.get()) as usize;
+            let page = state.flow.launch_template / PER_PAGE;
+            let pages = count.div_ceil(PER_PAGE);
+
+            if col < 2 {
+                state.flow.launch_template = (page + pages - 1) % pages * PER_PAGE;
+            } else if col >= WIDTH - 2 {
+                state.flow.launch_template = (page + 1) % pages * PER_PAGE;
+            } else if (col - 2) % (THUMB + GAP) < THUMB {
+                let slot = page * PER_PAGE + (col - 2) / (THUMB + GAP);
+                if slot >= count {
+                    return;
+                }
+                state.flow.launch_template = slot;
+                // Double-click starts from the template right away.
+                if is_open {
+                    state.flow.launch_start_new = true;
+                    state.editor.file_handle = None;
+                    state.editor.file_path = config().file_name.clone();
+                }
+            }
+        })
+        .on_state(|el, state| {
+            if state.flow.viewport_too_small || state.flow.phase != AppPhase::Launch {
+                return;
+            }
+            el.look(picker_look(state));
+            el.draw();
+        });
+
+    picker
+}
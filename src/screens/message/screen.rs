@@ -1,5 +1,8 @@
 use crate::{
-    core::model::{AppPhase, State},
+    core::{
+        config::config,
+        model::{AppPhase, State},
+    },
     platform,
     ui::{APP_HEIGHT, APP_WIDTH},
 };
@@ -24,7 +27,7 @@ pub fn build() -> Rectangle<State> {
             let initial_phase = io.initial_phase();
 
             // Clear the broken path and reset to default
-            state.editor.file_path = "favicon.svg".to_string();
+            state.editor.file_path = config().file_name.clone();
 
             if initial_phase == AppPhase::Splash {
                 state.flow.splash_started_ms = None;
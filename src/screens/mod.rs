@@ -1,4 +1,7 @@
 pub(crate) mod editor;
+pub(crate) mod gallery;
 pub(crate) mod launch;
 pub(crate) mod message;
+pub(crate) mod recover;
+pub(crate) mod settings;
 pub(crate) mod splash;
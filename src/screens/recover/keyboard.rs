@@ -0,0 +1,14 @@
+use crate::core::model::State;
+use incredible::*;
+
+/// Keyboard choice on the recovery screen.
+///
+/// - `Enter` / `r` — restore the unsaved work
+/// - `Esc` / `d` — discard it
+pub(crate) fn handle_key(state: &mut State, key: &Key) {
+    match key {
+        Key::Enter | Key::Char('r') => state.flow.recovery_restore = Some(true),
+        Key::Escape | Key::Char('d') => state.flow.recovery_restore = Some(false),
+        _ => {}
+    }
+}
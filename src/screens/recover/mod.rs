@@ -0,0 +1,3 @@
+pub(crate) mod keyboard;
+pub(crate) mod screen;
+pub(crate) mod ui;
@@ -0,0 +1,25 @@
+use crate::{
+    core::model::{AppPhase, State},
+    ui::{APP_HEIGHT, APP_WIDTH},
+};
+use incredible::*;
+use incredible_elements::Rectangle;
+use incredible_helpers_layout::Arrangers;
+
+pub fn build() -> Rectangle<State> {
+    let wrapper: Rectangle<State> = Rectangle::new();
+    wrapper.width(APP_WIDTH).height(APP_HEIGHT).fill(Some(' '));
+    wrapper.on_state(|el, state| {
+        el.showed(!state.flow.viewport_too_small && state.flow.phase == AppPhase::Recover);
+    });
+
+    wrapper.add(super::ui::recover_title::build());
+    wrapper.add(super::ui::button_restore::build());
+    wrapper.add(super::ui::button_discard::build());
+    wrapper.add(super::ui::recover_hint::build());
+    wrapper.elements_snap_center_x();
+
+    wrapper.showed(false);
+
+    wrapper
+}
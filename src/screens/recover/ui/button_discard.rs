@@ -0,0 +1,23 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static Y: isize = 16;
+
+pub fn build() -> TextButton<State> {
+    let button_discard: TextButton<State> = TextButton::default();
+    button_discard
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Discard")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.flow.recovery_restore = Some(false);
+            }
+        });
+
+    button_discard
+}
@@ -0,0 +1,23 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::TextButton;
+use incredible_helpers_styling::*;
+
+static Y: isize = 14;
+
+pub fn build() -> TextButton<State> {
+    let button_restore: TextButton<State> = TextButton::default();
+    button_restore
+        .y(Y)
+        .focused(false)
+        .pointer(Some(PointerShape::Pointer))
+        .text("Restore")
+        .underline(Some(UnderlineKind::Dotted))
+        .on_mouse(|_el, state, event| {
+            if event.mouse == Mouse::Click {
+                state.flow.recovery_restore = Some(true);
+            }
+        });
+
+    button_restore
+}
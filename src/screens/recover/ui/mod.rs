@@ -0,0 +1,4 @@
+pub(crate) mod button_discard;
+pub(crate) mod button_restore;
+pub(crate) mod recover_hint;
+pub(crate) mod recover_title;
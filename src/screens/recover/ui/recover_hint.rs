@@ -0,0 +1,16 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+use incredible_helpers_styling::*;
+
+static Y: isize = 11;
+
+pub fn build() -> Text<State> {
+    let recover_hint: Text<State> = Text::default();
+    recover_hint
+        .y(Y)
+        .faint(Some(true))
+        .text("A previous session ended before saving. Enter restores, Esc discards");
+
+    recover_hint
+}
@@ -0,0 +1,21 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+use incredible_helpers_styling::*;
+
+static Y: isize = 9;
+
+pub fn build() -> Text<State> {
+    let recover_title: Text<State> = Text::default();
+    recover_title.y(Y).bold(Some(true)).on_state(|el, state| {
+        let path = state
+            .flow
+            .recovery
+            .as_ref()
+            .map(|editor| editor.file_path.as_str())
+            .unwrap_or_default();
+        el.text(&format!("{:^60}", format!("Unsaved changes to {}", path)));
+    });
+
+    recover_title
+}
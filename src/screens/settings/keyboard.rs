@@ -0,0 +1,26 @@
+use super::ui::utils::*;
+use crate::core::model::State;
+use incredible::*;
+
+/// Keyboard navigation on the settings screen.
+///
+/// - up / down or `k` `j` — choose a setting
+/// - left / right or `h` `l` — change it
+/// - `Enter` — change it, or start typing a file name or key (`Enter` ends)
+/// - `Esc` / `q` — save and go back
+pub(crate) fn handle_key(state: &mut State, key: &Key) {
+    state.flow.status = None;
+    if type_setting_in_state(state, key) {
+        return;
+    }
+    match key {
+        Key::Up | Key::Char('k') => select_setting_in_state(state, -1),
+        Key::Down | Key::Char('j') => select_setting_in_state(state, 1),
+        Key::Left | Key::Char('h') => step_setting_in_state(state, false),
+        Key::Right | Key::Char('l') | Key::Enter | Key::Char(' ') => {
+            step_setting_in_state(state, true)
+        }
+        Key::Escape | Key::Char('q') => close_settings_in_state(state),
+        _ => {}
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{
+    core::model::{AppPhase, State},
+    ui::{APP_HEIGHT, APP_WIDTH},
+};
+use incredible::*;
+use incredible_elements::Rectangle;
+
+pub fn build() -> Rectangle<State> {
+    let wrapper: Rectangle<State> = Rectangle::new();
+    wrapper.width(APP_WIDTH).height(APP_HEIGHT).fill(Some(' '));
+    wrapper.on_state(|el, state| {
+        el.showed(!state.flow.viewport_too_small && state.flow.phase == AppPhase::Settings);
+    });
+
+    wrapper.add(super::ui::settings_title::build());
+    wrapper.add(super::ui::settings_list::build());
+    wrapper.add(super::ui::button_settings_back::build());
+    wrapper.add(super::ui::settings_hint::build());
+
+    wrapper.showed(false);
+
+    wrapper
+}
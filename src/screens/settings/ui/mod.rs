@@ -0,0 +1,5 @@
+pub(crate) mod button_settings_back;
+pub(crate) mod settings_hint;
+pub(crate) mod settings_list;
+pub(crate) mod settings_title;
+pub(crate) mod utils;
@@ -0,0 +1,24 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+use incredible_helpers_styling::*;
+
+static X: isize = 10;
+static Y: isize = 23;
+
+const HINT: &str = "Arrows choose and change, Enter edits, Esc saves and goes back";
+
+pub fn build() -> Text<State> {
+    let settings_hint: Text<State> = Text::default();
+    settings_hint
+        .x(X)
+        .y(Y)
+        .faint(Some(true))
+        .on_state(|el, state| {
+            // Notes, such as a refused key, show in place of the hint.
+            let text = state.flow.status.as_deref().unwrap_or(HINT);
+            el.text(&format!("{:<1$}", text, HINT.len()));
+        });
+
+    settings_hint
+}
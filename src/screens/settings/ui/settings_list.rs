@@ -0,0 +1,146 @@
+use super::utils::*;
+use crate::core::config::{Config, config};
+use crate::core::model::{AppPhase, State};
+use crate::ui::pixels::color_decor;
+use incredible::*;
+
+static X: isize = 2;
+static Y: isize = 2;
+
+/// Label column, then the value between its step arrows.
+const LABEL_COLS: usize = 20;
+const WIDTH: usize = 64;
+/// Text color of the selected row, on the theme's highlight.
+const SELECTED_TEXT: u8 = 16;
+
+fn put(row: &mut [Block], col: usize, text: &str, decor: Decor) {
+    for (i, ch) in text.chars().enumerate() {
+        if let Some(block) = row.get_mut(col + i) {
+            *block = Block::new(ch, decor.clone());
+        }
+    }
+}
+
+/// The value of `setting` as shown, swatches for colors.
+fn value_blocks(setting: Setting, config: &Config, editing: bool) -> Vec<Block> {
+    let text = |text: String| {
+        text.chars()
+            .map(|ch| Block::new(ch, Decor::default()))
+            .collect::<Vec<_>>()
+    };
+    let swatch = |code: u8| {
+        let mut blocks = vec![Block::new(' ', color_decor(None, Some(code))); 2];
+        blocks.extend(text(format!(" {}", code)));
+        blocks
+    };
+    let on_off = |on: bool| text(if on { "on" } else { "off" }.to_string());
+
+    match setting {
+        Setting::Size => text(format!("{}x{}", config.size, config.size)),
+        Setting::FileName if editing => text(format!("{}_", config.file_name)),
+        Setting::FileName => text(config.file_name.clone()),
+        Setting::Palette if config.palette.is_empty() => text("empty slots".to_string()),
+        Setting::Palette => config
+            .palette
+            .iter()
+            .map(|&code| Block::new(' ', color_decor(None, Some(code))))
+            .collect(),
+        Setting::ExportPx => text(format!("{} px", config.export_px)),
+        Setting::ExportLayers => text(
+            if config.export_layers {
+                "layer"
+            } else {
+                "flat"
+            }
+            .to_string(),
+        ),
+        Setting::Splash => on_off(config.splash),
+        Setting::SplashMs => text(format!("{} ms", config.splash_ms)),
+        Setting::Highlight => swatch(config.highlight),
+        Setting::Grid => swatch(config.grid),
+        Setting::Guide => swatch(config.guide),
+        Setting::Key(_) if editing => text("press a key".to_string()),
+        Setting::Key(action) => text(format!("'{}'", config.key(action))),
+    }
+}
+
+fn list_look(state: &State) -> Look {
+    let rows = settings();
+    let config = config();
+    let selected = color_decor(Some(SELECTED_TEXT), Some(config.highlight));
+
+    let blocks = rows
+        .iter()
+        .enumerate()
+        .map(|(i, &setting)| {
+            let is_selected = i == state.flow.settings_index;
+            let decor = if is_selected {
+                selected.clone()
+            } else {
+                Decor::default()
+            };
+            let mut row = vec![Block::new(' ', Decor::default()); WIDTH];
+            put(
+                &mut row,
+                0,
+                &format!("{:<1$}", setting.label(), LABEL_COLS - 1),
+                decor,
+            );
+
+            let editing = is_selected && state.flow.settings_editing;
+            let value = value_blocks(setting, &config, editing);
+            let arrows = !setting.is_typed();
+            if arrows {
+                put(&mut row, LABEL_COLS, "◀", Decor::default());
+            }
+            let start = LABEL_COLS + 2;
+            let end = (start + value.len()).min(WIDTH - 2);
+            for (i, block) in value.into_iter().take(end - start).enumerate() {
+                row[start + i] = block;
+            }
+            if arrows {
+                put(&mut row, end + 1, "▶", Decor::default());
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+    Look::from(blocks)
+}
+
+pub fn build() -> Element<State> {
+    let settings_list: Element<State> = Element::new();
+    settings_list
+        .x(X)
+        .y(Y)
+        .pointer(Some(PointerShape::Pointer))
+        .look(Look::from((WIDTH, settings().len(), ' ')))
+        .on_mouse(|el, state, event| {
+            if event.mouse != Mouse::Click {
+                return;
+            }
+            let col = event.x.saturating_sub(el.visual.x.get()) as usize;
+            let row = event.y.saturating_sub(el.visual.y.get()) as usize;
+            if row >= settings().len() {
+                return;
+            }
+            state.flow.settings_index = row;
+            state.flow.settings_editing = false;
+            state.flow.status = None;
+            // Clicking the label selects, the value steps forward and the
+            // left arrow back.
+            match col {
+                c if c < LABEL_COLS => {}
+                c if c < LABEL_COLS + 2 => step_setting_in_state(state, false),
+                _ => step_setting_in_state(state, true),
+            }
+        })
+        .on_state(|el, state| {
+            if state.flow.viewport_too_small || state.flow.phase != AppPhase::Settings {
+                return;
+            }
+            el.look(list_look(state));
+            el.draw();
+        });
+
+    settings_list
+}
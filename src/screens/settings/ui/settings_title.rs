@@ -0,0 +1,14 @@
+use crate::core::model::State;
+use incredible::*;
+use incredible_elements::Text;
+use incredible_helpers_styling::*;
+
+static X: isize = 1;
+static Y: isize = 0;
+
+pub fn build() -> Text<State> {
+    let settings_title: Text<State> = Text::default();
+    settings_title.x(X).y(Y).bold(Some(true)).text("Settings");
+
+    settings_title
+}
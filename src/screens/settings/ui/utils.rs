@@ -0,0 +1,193 @@
+use crate::core::config::{
+    Config, EXPORT_PIXEL_SIZES, KeyAction, SPLASH_DURATIONS, config, config_to_toml, set_config,
+};
+use crate::core::model::{AppPhase, CANVAS_SIZES, MAX_PALETTE_SLOTS, State};
+use crate::platform;
+use incredible::*;
+
+/// One row of the settings screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Setting {
+    Size,
+    FileName,
+    Palette,
+    ExportPx,
+    ExportLayers,
+    Splash,
+    SplashMs,
+    Highlight,
+    Grid,
+    Guide,
+    Key(KeyAction),
+}
+
+impl Setting {
+    pub(crate) fn label(self) -> String {
+        match self {
+            Setting::Size => "New icon size".to_string(),
+            Setting::FileName => "New file name".to_string(),
+            Setting::Palette => "New palette".to_string(),
+            Setting::ExportPx => "SVG pixel size".to_string(),
+            Setting::ExportLayers => "Save layers".to_string(),
+            Setting::Splash => "Splash screen".to_string(),
+            Setting::SplashMs => "Splash time".to_string(),
+            Setting::Highlight => "Highlight color".to_string(),
+            Setting::Grid => "Grid color".to_string(),
+            Setting::Guide => "Guide color".to_string(),
+            Setting::Key(action) => format!("Key: {}", action.name()),
+        }
+    }
+
+    /// Settings typed in rather than stepped through.
+    pub(crate) fn is_typed(self) -> bool {
+        matches!(self, Setting::FileName | Setting::Key(_))
+    }
+}
+
+/// Every row, top to bottom.
+pub(crate) fn settings() -> Vec<Setting> {
+    let mut rows = vec![
+        Setting::Size,
+        Setting::FileName,
+        Setting::Palette,
+        Setting::ExportPx,
+        Setting::ExportLayers,
+        Setting::Splash,
+        Setting::SplashMs,
+        Setting::Highlight,
+        Setting::Grid,
+        Setting::Guide,
+    ];
+    rows.extend(KeyAction::ALL.map(Setting::Key));
+    rows
+}
+
+fn selected_setting(state: &State) -> Setting {
+    let rows = settings();
+    rows[state.flow.settings_index.min(rows.len() - 1)]
+}
+
+/// The entry after (or before) `current` in `options`, wrapping.
+fn cycle<T: Copy + PartialEq>(options: &[T], current: T, forward: bool) -> T {
+    let count = options.len();
+    let index = options.iter().position(|&o| o == current).unwrap_or(0);
+    let next = if forward {
+        (index + 1) % count
+    } else {
+        (index + count - 1) % count
+    };
+    options[next]
+}
+
+fn step_code(code: u8, forward: bool) -> u8 {
+    if forward {
+        code.wrapping_add(1)
+    } else {
+        code.wrapping_sub(1)
+    }
+}
+
+/// Palette offered for new icons: the open icon's colors when the settings
+/// were opened from the editor, otherwise none (empty slots).
+fn document_palette(state: &State) -> Vec<u8> {
+    if state.flow.settings_return != AppPhase::Main {
+        return vec![];
+    }
+    let mut colors: Vec<u8> = vec![];
+    for code in state.editor.palette_colors.iter().flatten() {
+        if !colors.contains(code) {
+            colors.push(*code);
+        }
+    }
+    colors.truncate(MAX_PALETTE_SLOTS);
+    colors
+}
+
+/// Shows the settings, which go back to `return_to` when left.
+pub(crate) fn open_settings_in_state(state: &mut State, return_to: AppPhase) {
+    state.flow.settings_index = 0;
+    state.flow.settings_editing = false;
+    state.flow.settings_return = return_to;
+    state.flow.status = None;
+    state.flow.phase = AppPhase::Settings;
+}
+
+/// Saves the settings and goes back to the screen they were opened from.
+pub(crate) fn close_settings_in_state(state: &mut State) {
+    state.flow.settings_editing = false;
+    state.flow.status = None;
+    platform::get_io().save_config(&config_to_toml(&config()));
+    state.flow.phase = state.flow.settings_return.clone();
+}
+
+/// Moves the selection by `step` rows, stopping at either end.
+pub(crate) fn select_setting_in_state(state: &mut State, step: isize) {
+    let count = settings().len() as isize;
+    let index = state.flow.settings_index as isize + step;
+    if (0..count).contains(&index) {
+        state.flow.settings_index = index as usize;
+        state.flow.settings_editing = false;
+    }
+}
+
+/// Changes the selected setting to its next (or previous) value. Typed
+/// settings start being edited instead.
+pub(crate) fn step_setting_in_state(state: &mut State, forward: bool) {
+    let setting = selected_setting(state);
+    if setting.is_typed() {
+        state.flow.settings_editing = true;
+        return;
+    }
+
+    let mut config = (*config()).clone();
+    match setting {
+        Setting::Size => config.size = cycle(&CANVAS_SIZES, config.size, forward),
+        Setting::Palette => {
+            let document = document_palette(state);
+            config.palette = if config.palette.is_empty() {
+                document
+            } else {
+                vec![]
+            };
+        }
+        Setting::ExportPx => {
+            config.export_px = cycle(&EXPORT_PIXEL_SIZES, config.export_px, forward)
+        }
+        Setting::ExportLayers => config.export_layers = !config.export_layers,
+        Setting::Splash => config.splash = !config.splash,
+        Setting::SplashMs => config.splash_ms = cycle(&SPLASH_DURATIONS, config.splash_ms, forward),
+        Setting::Highlight => config.highlight = step_code(config.highlight, forward),
+        Setting::Grid => config.grid = step_code(config.grid, forward),
+        Setting::Guide => config.guide = step_code(config.guide, forward),
+        Setting::FileName | Setting::Key(_) => {}
+    }
+    set_config(config);
+}
+
+/// Typing into the setting being edited; returns whether `key` was taken.
+pub(crate) fn type_setting_in_state(state: &mut State, key: &Key) -> bool {
+    if !state.flow.settings_editing {
+        return false;
+    }
+    let mut config = (*config()).clone();
+    match (selected_setting(state), key) {
+        (Setting::Key(action), Key::Char(c)) => {
+            // A reserved key is refused with a note under the list.
+            state.flow.status = config.bind(action, *c).err();
+            state.flow.settings_editing = false;
+        }
+        (Setting::FileName, Key::Char(c)) => config.file_name.push(*c),
+        (Setting::FileName, Key::Backspace) => {
+            config.file_name.pop();
+        }
+        (_, Key::Enter | Key::Escape) => {
+            if config.file_name.trim().is_empty() {
+                config.file_name = Config::default().file_name;
+            }
+            state.flow.settings_editing = false;
+        }
+        _ => return true,
+    }
+    set_config(config);
+    true
+}
@@ -1,5 +1,8 @@
+use crate::core::config::config;
 use crate::core::model::{AppPhase, State};
+use crate::features::palette_file::palette_format;
 use crate::platform;
+use crate::screens::editor::ui::utils::load_palette_in_state;
 use incredible::*;
 use incredible_elements::App;
 use incredible_elements_extra::{FileDrop, FileDropOptions};
@@ -22,11 +25,20 @@ pub fn build() -> FileDrop<State> {
             return;
         };
 
+        // Palette files dropped while editing replace the palette instead.
+        if state.flow.phase == AppPhase::Main && palette_format(&item.name).is_some() {
+            let bytes = item
+                .read()
+                .map_err(|_| format!("Failed to read {}.", item.name));
+            load_palette_in_state(state, &item.name, bytes);
+            return;
+        }
+
         state.flow.launch_start_new = false;
         state.flow.launch_import_started = true;
         state.flow.phase = AppPhase::Splash;
         state.flow.splash_started_ms = None;
-        Globals::set_tick_rate(10.0);
+        Globals::set_tick_rate(config().idle_ticks as f64);
         platform::get_io().start_import_drop(item);
 
         // Redraw the whole app so the splash (and any other phase-dependent
@@ -1,6 +1,7 @@
 pub mod file_drop;
 pub mod logo;
 pub mod made_with;
+pub mod pixels;
 pub mod tagline;
 pub mod title_bar;
 pub mod viewport_guard;
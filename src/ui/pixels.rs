@@ -0,0 +1,68 @@
+use incredible::*;
+
+/// Decor with only the foreground and background colors set.
+pub fn color_decor(color: Option<u8>, background: Option<u8>) -> Decor {
+    Decor::new(
+        Some(false),
+        Some(false),
+        None,
+        None,
+        Some(false),
+        Some(false),
+        Some(false),
+        color.map(Color::Ansi),
+        background.map(Color::Ansi),
+    )
+}
+
+/// Two tones used to show transparency as a checkerboard.
+pub const CHECKER: [u8; 2] = [250, 245];
+
+/// Block for a void cell: `:` or, with `checkerboard`, a tone picked by `parity`.
+pub fn void_block(checkerboard: bool, parity: usize) -> Block {
+    if checkerboard {
+        Block::new(' ', color_decor(None, Some(CHECKER[parity % 2])))
+    } else {
+        Block::new(':', Decor::default())
+    }
+}
+
+/// Nearest-neighbour resample of a square grid to `target` x `target`.
+pub fn sample_grid(data: &[Option<u8>], size: usize, target: usize) -> Vec<Option<u8>> {
+    if size == target {
+        return data.to_vec();
+    }
+
+    (0..target * target)
+        .map(|i| {
+            let row = (i / target) * size / target;
+            let col = (i % target) * size / target;
+            data.get(row * size + col).copied().flatten()
+        })
+        .collect()
+}
+
+/// Renders a square grid at 1:1, two pixels per terminal cell using `▀`/`▄`.
+///
+/// Void pixels show `backdrop` (the terminal background when `None`).
+pub fn half_block_rows(data: &[Option<u8>], size: usize, backdrop: Option<u8>) -> Vec<Vec<Block>> {
+    (0..size.div_ceil(2))
+        .map(|pair| {
+            (0..size)
+                .map(|col| {
+                    let top = data.get(pair * 2 * size + col).copied().flatten();
+                    let bottom = data.get((pair * 2 + 1) * size + col).copied().flatten();
+                    match (top, bottom) {
+                        (Some(top), bottom) => {
+                            Block::new('▀', color_decor(Some(top), bottom.or(backdrop)))
+                        }
+                        (None, Some(bottom)) => {
+                            Block::new('▄', color_decor(Some(bottom), backdrop))
+                        }
+                        (None, None) => Block::new(' ', color_decor(None, backdrop)),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -12,6 +12,12 @@ fn render_title(el: &Text<State>, state: &State) {
         state.editor.file_path, state.editor.size, state.editor.size
     );
     line.replace_range(0..text.len().min(cols), &text);
+    if let Some(status) = &state.flow.status {
+        let start = cols.saturating_sub(status.chars().count() + 1);
+        if start > text.len() {
+            line.replace_range(start..start + status.len(), status);
+        }
+    }
     el.text(&line);
 }
 
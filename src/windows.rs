@@ -21,7 +21,7 @@ mod ui;
 #[cfg(feature = "windows-native")]
 pub use crate::core::io::RusticonIo;
 #[cfg(feature = "windows-native")]
-pub use crate::core::model::{AppPhase, MIN_SPLASH_MS, State};
+pub use crate::core::model::{AppPhase, State};
 
 #[cfg(feature = "windows-native")]
 fn main() {